@@ -38,14 +38,14 @@ lazy_static! {
 /// # Return
 /// * `(&str, &str)` - The folder where the file will be stored and the group file type
 /// # Errors
-/// * `&str` - An error message if the file is not a video or an image
-pub fn get_file_storage_group_from_bytes(buffer: &Vec<u8>) -> Result<(&str, &str), &str> {
+/// * `ValidationError` - If the file is not a video or an image
+pub fn get_file_storage_group_from_bytes(buffer: &[u8]) -> Result<(&'static str, &'static str), ValidationError> {
     if is_video(buffer) {
         Ok((STORAGE_VIDEOS_PATH, GROUP_VIDEO))
     } else if is_image(buffer) {
         Ok((STORAGE_IMAGES_PATH, GROUP_IMAGE))
     } else {
-        Err(INVALID_FILE_GROUP)
+        Err(ValidationError::InvalidFileGroup)
     }
 }
 
@@ -54,8 +54,8 @@ pub fn get_file_storage_group_from_bytes(buffer: &Vec<u8>) -> Result<(&str, &str
 /// * `buffer` - The file buffer
 /// # Return
 /// * `String` - The UUID generated in hyphenated format
-fn generate_uuid(buffer: &Vec<u8>) -> String {
-    uuid::Uuid::new_v5(&Uuid::default(), &buffer)
+fn generate_uuid(buffer: &[u8]) -> String {
+    uuid::Uuid::new_v5(&Uuid::default(), buffer)
         .to_hyphenated().to_string()
 }
 
@@ -67,18 +67,16 @@ fn generate_uuid(buffer: &Vec<u8>) -> String {
 /// * `String` - The success message
 /// # Errors
 /// * `String` - An error message if the file is not valid or if the file is already stored
-/// or if an other error occurs
+///   or if an other error occurs
 fn upload_file(file_path: &String) -> Result<String, String> {
     // Read file
-    let buffer;
-    match read_from_path(file_path) {
-        Ok(buf) => buffer = buf,
+    let buffer = match read_from_path(file_path) {
+        Ok(buf) => buf,
         Err(error) => return Err(format!("{}", error)),
     };
 
-    let file_valid;
-    match validate_file(file_path, true) {
-        Ok(valid) => file_valid = valid,
+    let file_valid = match validate_file(file_path, true) {
+        Ok(valid) => valid,
         Err(_) => return Err(INVALID_FILE_CONTENT.to_string()),
     };
 
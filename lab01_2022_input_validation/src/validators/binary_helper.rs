@@ -0,0 +1,118 @@
+/// Give the bytes of a buffer at the given offset
+/// # Arguments
+/// * `buffer` - The buffer to read
+/// * `offset` - The offset of the first byte
+/// # Returns
+/// * `Option<[u8; N]>` - The bytes, None if they exceed the buffer
+fn read_bytes<const N: usize>(buffer: &[u8], offset: usize) -> Option<[u8; N]> {
+    buffer.get(offset..offset.checked_add(N)?)?.try_into().ok()
+}
+
+/// Read a big-endian 16 bits integer
+/// # Arguments
+/// * `buffer` - The buffer to read
+/// * `offset` - The offset of the integer
+/// # Returns
+/// * `Option<u16>` - The integer, None if it exceeds the buffer
+pub fn read_u16_be(buffer: &[u8], offset: usize) -> Option<u16> {
+    read_bytes(buffer, offset).map(u16::from_be_bytes)
+}
+
+/// Read a little-endian 16 bits integer
+/// # Arguments
+/// * `buffer` - The buffer to read
+/// * `offset` - The offset of the integer
+/// # Returns
+/// * `Option<u16>` - The integer, None if it exceeds the buffer
+pub fn read_u16_le(buffer: &[u8], offset: usize) -> Option<u16> {
+    read_bytes(buffer, offset).map(u16::from_le_bytes)
+}
+
+/// Read a big-endian 32 bits integer
+/// # Arguments
+/// * `buffer` - The buffer to read
+/// * `offset` - The offset of the integer
+/// # Returns
+/// * `Option<u32>` - The integer, None if it exceeds the buffer
+pub fn read_u32_be(buffer: &[u8], offset: usize) -> Option<u32> {
+    read_bytes(buffer, offset).map(u32::from_be_bytes)
+}
+
+/// Read a little-endian 32 bits integer
+/// # Arguments
+/// * `buffer` - The buffer to read
+/// * `offset` - The offset of the integer
+/// # Returns
+/// * `Option<u32>` - The integer, None if it exceeds the buffer
+pub fn read_u32_le(buffer: &[u8], offset: usize) -> Option<u32> {
+    read_bytes(buffer, offset).map(u32::from_le_bytes)
+}
+
+/// Read a big-endian 64 bits integer
+/// # Arguments
+/// * `buffer` - The buffer to read
+/// * `offset` - The offset of the integer
+/// # Returns
+/// * `Option<u64>` - The integer, None if it exceeds the buffer
+pub fn read_u64_be(buffer: &[u8], offset: usize) -> Option<u64> {
+    read_bytes(buffer, offset).map(u64::from_be_bytes)
+}
+
+/// Read a little-endian 64 bits integer
+/// # Arguments
+/// * `buffer` - The buffer to read
+/// * `offset` - The offset of the integer
+/// # Returns
+/// * `Option<u64>` - The integer, None if it exceeds the buffer
+pub fn read_u64_le(buffer: &[u8], offset: usize) -> Option<u64> {
+    read_bytes(buffer, offset).map(u64::from_le_bytes)
+}
+
+/// Give the name of a four characters code such as a PNG chunk type, the
+/// non printable bytes being escaped
+/// # Arguments
+/// * `code` - The four bytes of the code
+/// # Returns
+/// * `String` - The printable name of the code
+pub fn four_cc_name(code: &[u8]) -> String {
+    code.iter().map(|&byte| {
+        if byte.is_ascii_graphic() || byte == b' ' { (byte as char).to_string() } else { format!("\\x{:02x}", byte) }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::binary_helper::{read_u16_be, read_u16_le, read_u32_be, read_u32_le, read_u64_be,
+                                           read_u64_le, four_cc_name};
+
+    #[test]
+    fn read_integers() {
+        let buffer = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+
+        // Pass
+        assert_eq!(read_u16_be(&buffer, 0), Some(0x0102));
+        assert_eq!(read_u16_le(&buffer, 6), Some(0x0807));
+        assert_eq!(read_u32_be(&buffer, 4), Some(0x05060708));
+        assert_eq!(read_u32_le(&buffer, 0), Some(0x04030201));
+        assert_eq!(read_u64_be(&buffer, 0), Some(0x0102030405060708));
+        assert_eq!(read_u64_le(&buffer, 0), Some(0x0807060504030201));
+
+        // Fail
+        assert_eq!(read_u16_be(&buffer, 7), None);
+        assert_eq!(read_u32_le(&buffer, 5), None);
+        assert_eq!(read_u64_be(&buffer, 1), None);
+
+        // Corner cases
+        assert_eq!(read_u32_be(&buffer, usize::MAX), None);
+    }
+
+    #[test]
+    fn four_cc_name_classical() {
+        // Pass
+        assert_eq!(four_cc_name(b"IHDR"), "IHDR");
+        assert_eq!(four_cc_name(b"avc "), "avc ");
+
+        // Corner cases
+        assert_eq!(four_cc_name(&[0x00, b'a', 0xff, b'b']), "\\x00a\\xffb");
+    }
+}
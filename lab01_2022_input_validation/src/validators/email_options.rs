@@ -0,0 +1,36 @@
+use crate::validators::url_options::{UrlOptions};
+
+/// Syntax accepted for the local part of an email address, before the "@"
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LocalPartPolicy {
+    /// Only dot-atoms such as "first.last+tag" are accepted, as most mail providers do
+    #[default]
+    DotAtom,
+    /// Quoted strings such as "\"john..doe\"" are also accepted (RFC 5322 section 3.4.1)
+    AllowQuotedString,
+}
+
+/// Options used to validate an email address
+#[derive(Debug, Clone, Default)]
+pub struct EmailOptions {
+    /// Syntax accepted for the local part
+    pub local_part_policy: LocalPartPolicy,
+    /// Options applied to the domain as to the host of an url, such as the top level
+    /// domains whitelist. The options about the scheme are ignored and the options
+    /// about the port, the userinfo and the path are not used
+    pub domain_options: UrlOptions,
+}
+
+impl EmailOptions {
+    /// Create options only restricting the top level domains
+    /// # Arguments
+    /// * `top_level_domains_whitelist` - a list of top level domains
+    /// # Returns
+    /// * `EmailOptions` - The options with the given whitelist and default values otherwise
+    pub fn from_whitelist(top_level_domains_whitelist: Option<&Vec<&str>>) -> EmailOptions {
+        EmailOptions {
+            domain_options: UrlOptions::from_whitelist(top_level_domains_whitelist),
+            ..EmailOptions::default()
+        }
+    }
+}
@@ -1,10 +1,18 @@
 /// URL Messages
+pub static INVALID_URL: &str = "URL given is invalid";
 pub static INVALID_WHITELIST_TOP_LEVEL_DOMAIN: &str = "A top level domain given in whitelist is not valid";
+pub static INVALID_WHITELIST_SCHEME: &str = "A scheme given in whitelist is not valid";
+pub static INVALID_PUBLIC_SUFFIX_LIST: &str = "Public suffix list given is invalid";
+pub static INVALID_HOST_LIST: &str = "An entry of the host list given is invalid";
+
+/// Email messages
+pub static INVALID_EMAIL: &str = "Email address given is invalid";
 
 /// File messages
 pub static INVALID_FILE_PATH: &str = "File path given is invalid";
 pub static INVALID_FILE_GROUP: &str = "File group given is invalid";
 pub static INVALID_FILE_TYPE: &str = "File type given is invalid";
+pub static INVALID_FILE_STRUCTURE: &str = "File structure given is invalid";
 pub static ERROR_READING_FILE: &str = "An error occured while reading the file";
 
 /// UUID
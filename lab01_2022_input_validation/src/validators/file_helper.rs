@@ -2,7 +2,7 @@ use std::fs;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::validators::error_messages::{INVALID_FILE_PATH, ERROR_READING_FILE};
+use crate::validators::validation_error::{ValidationError};
 
 /// This regex only accept classical char for a file name / path
 static REGEX_FILE_PATH: &str = r"[a-zA-Z0-9/.\\_ :-]+";
@@ -23,18 +23,13 @@ pub fn is_valid_file_path(file_path: &str) -> bool {
 /// # Arguments
 /// * `file_path` - The file_path to read
 /// # Returns
-/// * `Vec<u8>` - The content of the file in bytes, or an error message
-/// if the path is invalid or is not readable
+/// * `Vec<u8>` - The content of the file in bytes
 /// # Errors
-/// * `&str` - The error message
-pub fn read_from_path(file_path: &str) -> Result<Vec<u8>, &str> {
+/// * `ValidationError` - If the path is invalid or the file is not readable
+pub fn read_from_path(file_path: &str) -> Result<Vec<u8>, ValidationError> {
     if !is_valid_file_path(file_path) {
-        return Err(INVALID_FILE_PATH);
+        return Err(ValidationError::InvalidFilePath);
     }
 
-    let buffer = fs::read(file_path);
-    return match buffer {
-        Ok(buffer) => Ok(buffer),
-        Err(_) => Err(ERROR_READING_FILE),
-    };
+    fs::read(file_path).map_err(|error| ValidationError::ReadingFile(error.kind()))
 }
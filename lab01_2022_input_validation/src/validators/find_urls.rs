@@ -0,0 +1,183 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::validators::url_validator::{UrlValidator};
+use crate::validators::url_options::{UrlOptions};
+use crate::validators::public_suffix_list::{PublicSuffixList};
+use crate::validators::idn_helper::{host_to_ascii};
+use crate::validators::validation_error::{ValidationError};
+
+// Urls starting with a scheme or "www." followed by a host, and bare domains such as "example.com/a"
+static REGEX_URL_CANDIDATE: &str = r#"(?i:\b[a-z][a-z\d+.-]*://[\p{L}\p{N}\[]|\bwww\.[\p{L}\p{N}])[^\s<>"]*|\b(?:[\p{L}\p{N}](?:[\p{L}\p{N}-]*[\p{L}\p{N}])?\.)+\p{L}{2,}\b(?:[:/?#][^\s<>"]*)?"#;
+// Punctuation ending a sentence rather than an url
+static TRAILING_PUNCTUATION: &str = ".,;:!?'\"";
+
+/// Url found in a text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundUrl<'a> {
+    /// The url as written in the text
+    pub url: &'a str,
+    /// The byte offset of the first character of the url in the text
+    pub start: usize,
+    /// The byte offset following the last character of the url in the text
+    pub end: usize,
+    /// True if the url is valid, false otherwise
+    pub valid: bool,
+}
+
+/// Remove the punctuation following an url, such as the full stop ending a sentence.
+/// A closing bracket is kept if it closes a bracket opened in the url
+/// # Arguments
+/// * `candidate` - The url followed by punctuation
+/// # Returns
+/// * `&str` - The url without trailing punctuation
+fn trim_trailing_punctuation(candidate: &str) -> &str {
+    let mut url = candidate;
+
+    while let Some(last) = url.chars().last() {
+        let opening = match last {
+            ')' => Some('('),
+            ']' => Some('['),
+            '}' => Some('{'),
+            _ => None,
+        };
+        let unbalanced = opening.is_some_and(|opening| {
+            url.matches(opening).count() < url.matches(last).count()
+        });
+        if !TRAILING_PUNCTUATION.contains(last) && !unbalanced {
+            break;
+        }
+        url = &url[..url.len() - last.len_utf8()];
+    }
+    url
+}
+
+/// Check if a bare domain such as "example.com" ends with a known public suffix,
+/// so that words such as "file.txt" are not taken for urls
+/// # Arguments
+/// * `candidate` - The bare domain, possibly followed by a port and a path
+/// * `options` - The options containing the public suffix list
+/// # Returns
+/// * `bool` - True if the domain has a registrable domain, false otherwise
+fn is_known_domain(candidate: &str, options: &UrlOptions) -> bool {
+    let host = &candidate[..candidate.find([':', '/', '?', '#']).unwrap_or(candidate.len())];
+    let list = options.public_suffix_list.as_deref().unwrap_or(PublicSuffixList::embedded());
+    host_to_ascii(host).is_some_and(|host| list.registrable_domain(&host).is_some())
+}
+
+/// Find every url of a given text and validate it with the given options
+/// # Arguments
+/// * `text` - The text to scan, such as a comment
+/// * `options` - The options the urls must respect
+/// # Return
+/// * `Vec<FoundUrl>` - The urls in order of appearance, with their position and validity
+/// # Errors
+/// * `ValidationError` - If the options are not valid
+pub fn find_urls_with_options<'a>(text: &'a str, options: &UrlOptions) -> Result<Vec<FoundUrl<'a>>, ValidationError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(REGEX_URL_CANDIDATE).unwrap();
+        static ref RE_EXPLICIT: Regex = Regex::new(r"^(?i:[a-z][a-z\d+.-]*://|www\.)").unwrap();
+    }
+    let validator = UrlValidator::new(options.clone())?;
+
+    let mut urls = Vec::new();
+    for candidate in RE.find_iter(text) {
+        let url = trim_trailing_punctuation(candidate.as_str());
+        let explicit = RE_EXPLICIT.is_match(url);
+
+        // A bare domain preceded by "@" is the domain of an email address and one
+        // followed by "@" is its local part
+        let email = text[..candidate.start()].ends_with('@') || text[candidate.end()..].starts_with('@');
+        if url.is_empty() || (!explicit && (email || !is_known_domain(url, options))) {
+            continue;
+        }
+        urls.push(FoundUrl {
+            url,
+            start: candidate.start(),
+            end: candidate.start() + url.len(),
+            valid: validator.validate(url),
+        });
+    }
+    Ok(urls)
+}
+
+/// Find every url of a given text and validate it
+/// # Arguments
+/// * `text` - The text to scan, such as a comment
+/// * `top_level_domains_whitelist` - a list of top level domains
+/// # Return
+/// * `Vec<FoundUrl>` - The urls in order of appearance, with their position and validity
+/// # Errors
+/// * `ValidationError` - If the whitelist is not valid
+pub fn find_urls<'a>(text: &'a str, top_level_domains_whitelist: Option<&Vec<&str>>) -> Result<Vec<FoundUrl<'a>>, ValidationError> {
+    find_urls_with_options(text, &UrlOptions::from_whitelist(top_level_domains_whitelist))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::find_urls::{find_urls, find_urls_with_options, FoundUrl};
+    use crate::validators::url_options::{UrlOptions, SsrfPolicy};
+    use crate::validators::validation_error::{ValidationError};
+
+    /// Give the urls found in a text
+    fn urls<'a>(found: &[FoundUrl<'a>]) -> Vec<&'a str> {
+        found.iter().map(|url| url.url).collect()
+    }
+
+    #[test]
+    fn find_urls_classical() {
+        let text = "See https://example.com/a?b=c, www.heig-vd.ch and example.co.uk/path.";
+
+        // Pass
+        let found = find_urls(text, None).unwrap();
+        assert_eq!(urls(&found), vec!["https://example.com/a?b=c", "www.heig-vd.ch", "example.co.uk/path"]);
+        assert_eq!(found[0], FoundUrl { url: "https://example.com/a?b=c", start: 4, end: 29, valid: true });
+        for url in &found {
+            assert_eq!(&text[url.start..url.end], url.url);
+            assert!(url.valid);
+        }
+
+        // Fail
+        assert!(find_urls("no link in this sentence, nor in notes.txt or v1.2.3", None).unwrap().is_empty());
+        assert!(find_urls("write to contact@example.com", None).unwrap().is_empty());
+        assert!(find_urls("write to first.name@example.com or info.ch@heig-vd.ch", None).unwrap().is_empty());
+
+        // Corner cases
+        assert!(find_urls("", None).unwrap().is_empty());
+        assert_eq!(urls(&find_urls("Visit münchen.de!", None).unwrap()), vec!["münchen.de"]);
+    }
+
+    #[test]
+    fn find_urls_trailing_punctuation() {
+        // Pass
+        assert_eq!(urls(&find_urls("(see https://example.com/a)", None).unwrap()), vec!["https://example.com/a"]);
+        assert_eq!(urls(&find_urls("https://en.wikipedia.org/wiki/Rust_(language).", None).unwrap()),
+                   vec!["https://en.wikipedia.org/wiki/Rust_(language)"]);
+        assert_eq!(urls(&find_urls("\"https://example.com/\"?!", None).unwrap()), vec!["https://example.com/"]);
+        assert_eq!(urls(&find_urls("<https://example.com/a>", None).unwrap()), vec!["https://example.com/a"]);
+
+        // Corner cases
+        assert_eq!(urls(&find_urls("is it example.com?", None).unwrap()), vec!["example.com"]);
+        assert!(find_urls("https://...", None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn find_urls_validation() {
+        let whitelist = vec![".ch"];
+        let text = "ok: https://heig-vd.ch/ bad: https://example.com/ and http://exa$mple.ch/";
+
+        // Pass
+        let found = find_urls(text, Some(&whitelist)).unwrap();
+        assert_eq!(found.iter().map(|url| url.valid).collect::<Vec<bool>>(), vec![true, false, false]);
+        let options = UrlOptions {
+            ssrf_policy: SsrfPolicy::Enabled,
+            ..UrlOptions::default()
+        };
+        let found = find_urls_with_options("http://localhost/ and http://192.168.1.1/admin", &options).unwrap();
+        assert_eq!(urls(&found), vec!["http://localhost/", "http://192.168.1.1/admin"]);
+        assert!(found.iter().all(|url| !url.valid));
+
+        // Fail
+        assert_eq!(find_urls(text, Some(&vec!["ch"])), Err(ValidationError::InvalidWhitelistTopLevelDomain(0)));
+    }
+}
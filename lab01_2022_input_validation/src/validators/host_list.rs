@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+
+use crate::validators::file_helper::{read_from_path};
+use crate::validators::idn_helper::{host_to_ascii};
+use crate::validators::validate_hostname::{validate_hostname};
+use crate::validators::validation_error::{ValidationError};
+
+/// Prefix of the entries matching every host of a registrable domain
+static REGISTRABLE_DOMAIN_PREFIX: &str = "registrable:";
+
+/// List of hosts used to allow or deny urls, stored in lower case ASCII form.
+/// An entry is either an exact host such as "www.example.com", a wildcard such as
+/// "*.example.com" matching the sub domains only, or a registrable domain such as
+/// "registrable:example.co.uk" matching every host whose registrable domain it is
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostList {
+    /// Exact hosts such as "www.example.com"
+    hosts: HashSet<String>,
+    /// Wildcards such as "*.example.com", stored without the "*." prefix
+    wildcards: HashSet<String>,
+    /// Registrable domains such as "example.co.uk", stored without prefix
+    registrable_domains: HashSet<String>,
+}
+
+impl HostList {
+    /// Parse a list of hosts
+    /// # Arguments
+    /// * `content` - The entries, one per line, empty lines and lines starting
+    ///   with "#" are ignored
+    /// # Returns
+    /// * `HostList` - The parsed list
+    /// # Errors
+    /// * `ValidationError` - If an entry is not valid, contains its line number
+    pub fn parse(content: &str) -> Result<HostList, ValidationError> {
+        let mut list = HostList::default();
+
+        for (index, line) in content.lines().enumerate() {
+            let entry = line.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let (set, host) = if let Some(host) = entry.strip_prefix(REGISTRABLE_DOMAIN_PREFIX) {
+                (&mut list.registrable_domains, host)
+            } else if let Some(host) = entry.strip_prefix("*.") {
+                (&mut list.wildcards, host)
+            } else {
+                (&mut list.hosts, entry)
+            };
+
+            // Unicode entries are compared in ASCII form like the hosts
+            match host_to_ascii(host).filter(|host| validate_hostname(host)) {
+                Some(host) => set.insert(host.to_ascii_lowercase()),
+                None => return Err(ValidationError::InvalidHostList(index + 1)),
+            };
+        }
+        Ok(list)
+    }
+
+    /// Load a list of hosts from a text file with one entry per line
+    /// # Arguments
+    /// * `file_path` - The path of the list
+    /// # Returns
+    /// * `HostList` - The parsed list
+    /// # Errors
+    /// * `ValidationError` - If the file can't be read, is not valid utf-8 or
+    ///   contains an invalid entry
+    pub fn from_file(file_path: &str) -> Result<HostList, ValidationError> {
+        let content = String::from_utf8(read_from_path(file_path)?).map_err(|error| {
+            let valid = &error.as_bytes()[..error.utf8_error().valid_up_to()];
+            ValidationError::InvalidHostList(valid.iter().filter(|&&byte| byte == b'\n').count() + 1)
+        })?;
+        HostList::parse(&content)
+    }
+
+    /// Check if the list contains no entry
+    /// # Returns
+    /// * `bool` - True if the list is empty, false otherwise
+    pub fn is_empty(&self) -> bool {
+        self.hosts.is_empty() && self.wildcards.is_empty() && self.registrable_domains.is_empty()
+    }
+
+    /// Check if a host matches an entry of the list
+    /// # Arguments
+    /// * `host` - The host in ASCII form
+    /// * `registrable_domain` - The registrable domain of the host, if known
+    /// # Returns
+    /// * `bool` - True if the host matches an entry, false otherwise
+    pub fn matches(&self, host: &str, registrable_domain: Option<&str>) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+
+        // Every parent domain of the host is compared with the wildcards
+        let wildcard = host.match_indices('.')
+            .any(|(index, _)| self.wildcards.contains(&host[index + 1..]));
+        let registrable = registrable_domain
+            .is_some_and(|domain| self.registrable_domains.contains(&domain.to_ascii_lowercase()));
+
+        self.hosts.contains(&host) || wildcard || registrable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use crate::validators::host_list::{HostList};
+    use crate::validators::validation_error::{ValidationError};
+    use crate::validators::test_helper::{BASE_FILE_PATH};
+
+    static OTHERS_FOLDER: &str = "others/";
+    static NAMING_CONVENTION : &str = "file_example_";
+
+    static LIST: &str = "# company hosts\n\
+                         www.example.com\n\
+                         \n\
+                         *.ourcompany.ch\n\
+                         registrable:example.co.uk\n\
+                         bücher.de\n";
+
+    #[test]
+    fn host_list_parse() {
+        // Pass
+        assert!(!HostList::parse(LIST).unwrap().is_empty());
+        assert!(HostList::parse("# only a comment\n\n").unwrap().is_empty());
+
+        // Fail
+        assert_eq!(HostList::parse("example.com\nexa$mple.com"), Err(ValidationError::InvalidHostList(2)));
+        assert_eq!(HostList::parse("*.*.example.com"), Err(ValidationError::InvalidHostList(1)));
+        assert_eq!(HostList::parse("example.*"), Err(ValidationError::InvalidHostList(1)));
+        assert_eq!(HostList::parse("registrable:"), Err(ValidationError::InvalidHostList(1)));
+    }
+
+    #[test]
+    fn host_list_matches() {
+        let list = HostList::parse(LIST).unwrap();
+
+        // Pass
+        assert!(list.matches("www.example.com", Some("example.com")));
+        assert!(list.matches("WWW.Example.COM", None));
+        assert!(list.matches("intranet.ourcompany.ch", Some("ourcompany.ch")));
+        assert!(list.matches("a.b.ourcompany.ch", Some("ourcompany.ch")));
+        assert!(list.matches("example.co.uk", Some("example.co.uk")));
+        assert!(list.matches("shop.example.co.uk", Some("example.co.uk")));
+        assert!(list.matches("xn--bcher-kva.de", Some("xn--bcher-kva.de")));
+
+        // Fail
+        assert!(!list.matches("example.com", Some("example.com")));
+        assert!(!list.matches("ourcompany.ch", Some("ourcompany.ch")));
+        assert!(!list.matches("evilourcompany.ch", Some("evilourcompany.ch")));
+        assert!(!list.matches("ourcompany.ch.evil.com", Some("evil.com")));
+        assert!(!list.matches("example.co.uk.evil.com", Some("evil.com")));
+
+        // Corner cases
+        // registrable domain entries rely on the registrable domain of the host
+        assert!(!list.matches("shop.example.co.uk", None));
+    }
+
+    #[test]
+    fn host_list_from_file() {
+        // Pass
+        let list = HostList::from_file(
+            &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "txt.txt")).unwrap();
+        assert!(list.matches("testestestestestestest", None));
+
+        // Fail
+        assert_eq!(HostList::from_file(
+            &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "csv.csv")),
+                   Err(ValidationError::InvalidHostList(1)));
+        assert_eq!(HostList::from_file(
+            &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "test.test")),
+                   Err(ValidationError::ReadingFile(ErrorKind::NotFound)));
+        assert!(matches!(HostList::from_file(
+            &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "xlsx.xlsx")),
+                         Err(ValidationError::InvalidHostList(_))));
+
+        // Corner cases
+        assert!(HostList::from_file(
+            &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "docx.docx")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,26 @@
+use std::fmt;
+use std::io;
+use std::net::{IpAddr, ToSocketAddrs};
+
+/// Resolver used to find the addresses of a host, so that the addresses can be checked
+/// before the url is fetched
+pub trait HostResolver: fmt::Debug + Send + Sync {
+    /// Resolve the given host
+    /// # Arguments
+    /// * `host` - The host in lower case ASCII form
+    /// # Returns
+    /// * `Vec<IpAddr>` - The addresses of the host
+    /// # Errors
+    /// * `io::Error` - If the host can't be resolved
+    fn resolve(&self, host: &str) -> io::Result<Vec<IpAddr>>;
+}
+
+/// Resolver using the resolver of the operating system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemResolver;
+
+impl HostResolver for SystemResolver {
+    fn resolve(&self, host: &str) -> io::Result<Vec<IpAddr>> {
+        Ok((host, 0).to_socket_addrs()?.map(|address| address.ip()).collect())
+    }
+}
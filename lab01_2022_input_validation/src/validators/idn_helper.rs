@@ -0,0 +1,109 @@
+use idna::{domain_to_ascii_cow, domain_to_unicode, AsciiDenyList};
+use unicode_security::{skeleton, RestrictionLevel, RestrictionLevelDetection};
+
+/// Prefix of the labels encoded in punycode
+static ACE_PREFIX: &str = "xn--";
+
+/// Check if the given host is an internationalized domain name, either in
+/// unicode form or containing punycode labels
+/// # Arguments
+/// * `host` - The host to check
+/// # Returns
+/// * `bool` - True if the host needs IDNA processing, false otherwise
+pub fn is_internationalized_host(host: &str) -> bool {
+    !host.is_ascii() || host.split('.').any(|label| label.to_ascii_lowercase().starts_with(ACE_PREFIX))
+}
+
+/// Convert a host to its ASCII form (A-labels) with the UTS-46 processing
+/// # Arguments
+/// * `host` - The host in unicode or punycode form
+/// # Returns
+/// * `Option<String>` - The host in ASCII form, None if the host can't be converted
+///   or contains an invalid punycode label
+pub fn host_to_ascii(host: &str) -> Option<String> {
+    domain_to_ascii_cow(host.as_bytes(), AsciiDenyList::URL).ok().map(|host| host.into_owned())
+}
+
+/// Convert a host to its unicode form (U-labels) with the UTS-46 processing
+/// # Arguments
+/// * `host` - The host in unicode or punycode form
+/// # Returns
+/// * `Option<String>` - The host in unicode form, None if the host contains an invalid label
+pub fn host_to_unicode(host: &str) -> Option<String> {
+    match domain_to_unicode(host) {
+        (host, Ok(())) => Some(host),
+        _ => None,
+    }
+}
+
+/// Check if a host in unicode form could be a lookalike of another domain
+/// # Arguments
+/// * `unicode_host` - The host in unicode form
+/// # Returns
+/// * `bool` - True if a label mixes scripts such as latin and cyrillic or is only made
+///   of characters confusable with ASCII, false otherwise
+pub fn is_confusable_host(unicode_host: &str) -> bool {
+    // https://www.unicode.org/reports/tr39/#Restriction_Level_Detection
+    unicode_host.split('.').any(|label| {
+        !label.check_restriction_level(RestrictionLevel::HighlyRestrictive)
+            || (!label.is_ascii() && skeleton(label).all(|c| c.is_ascii()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::idn_helper::{is_internationalized_host, host_to_ascii, host_to_unicode,
+                                        is_confusable_host};
+
+    #[test]
+    fn host_to_ascii_classical() {
+        // Pass
+        assert_eq!(host_to_ascii("münchen.de"), Some("xn--mnchen-3ya.de".to_string()));
+        assert_eq!(host_to_ascii("例え.jp"), Some("xn--r8jz45g.jp".to_string()));
+        assert_eq!(host_to_ascii("xn--mnchen-3ya.de"), Some("xn--mnchen-3ya.de".to_string()));
+
+        // Fail
+        assert_eq!(host_to_ascii("xn--a.de"), None);
+        assert_eq!(host_to_ascii("exa mple.de"), None);
+
+        // Corner cases
+        // UTS-46 maps upper case and full width full stops
+        assert_eq!(host_to_ascii("MÜNCHEN。de"), Some("xn--mnchen-3ya.de".to_string()));
+    }
+
+    #[test]
+    fn host_to_unicode_classical() {
+        // Pass
+        assert_eq!(host_to_unicode("xn--mnchen-3ya.de"), Some("münchen.de".to_string()));
+        assert_eq!(host_to_unicode("example.com"), Some("example.com".to_string()));
+
+        // Fail
+        assert_eq!(host_to_unicode("xn--a.de"), None);
+    }
+
+    #[test]
+    fn is_internationalized_host_classical() {
+        // Pass
+        assert!(is_internationalized_host("münchen.de"));
+        assert!(is_internationalized_host("XN--mnchen-3ya.de"));
+
+        // Fail
+        assert!(!is_internationalized_host("example.com"));
+        assert!(!is_internationalized_host("axn--b.com"));
+    }
+
+    #[test]
+    fn is_confusable_host_classical() {
+        // Pass
+        assert!(!is_confusable_host("münchen.de"));
+        assert!(!is_confusable_host("παράδειγμα.gr"));
+        assert!(!is_confusable_host("日本語テスト.jp"));
+        assert!(!is_confusable_host("example.com"));
+
+        // Fail
+        // latin "p" mixed with a cyrillic "а"
+        assert!(is_confusable_host("p\u{430}ypal.com"));
+        // only cyrillic characters looking like "apple"
+        assert!(is_confusable_host("\u{430}\u{440}\u{440}\u{4cf}\u{435}.com"));
+    }
+}
@@ -0,0 +1,282 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::validators::percent_encoding_helper::{percent_decode_str};
+
+/// Zone identifier chars accepted by RFC 6874, "%" is only allowed for percent-encoding
+static REGEX_ZONE_ID: &str = r"([a-zA-Z\d._~-]|%[[:xdigit:]]{2})+";
+
+/// Parse an IPv4 host in dotted-quad format such as "192.168.1.1"
+/// # Arguments
+/// * `host` - The host to parse
+/// # Returns
+/// * `Option<Ipv4Addr>` - The address, None if the host is not a dotted-quad address
+pub fn parse_ipv4_host(host: &str) -> Option<Ipv4Addr> {
+    // The standard parser refuses leading zeros which could be read as octal
+    host.parse().ok()
+}
+
+/// Parse a bracketed IPv6 host such as "[::1]" or "[fe80::1%25eth0]"
+/// # Arguments
+/// * `host` - The host to parse, including the brackets
+/// # Returns
+/// * `Option<(Ipv6Addr, Option<String>)>` - The address and its decoded zone identifier,
+///   None if the host is not a valid IPv6 literal
+pub fn parse_ipv6_host(host: &str) -> Option<(Ipv6Addr, Option<String>)> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(&format!("^{}$", REGEX_ZONE_ID)).unwrap();
+    }
+
+    let literal = host.strip_prefix('[')?.strip_suffix(']')?;
+    // The zone identifier is separated with an encoded "%" (RFC 6874)
+    let (address, zone_id) = match literal.split_once("%25") {
+        Some((address, zone_id)) if RE.is_match(zone_id) => (address, Some(percent_decode_str(zone_id)?)),
+        Some(_) => return None,
+        None => (literal, None),
+    };
+
+    Some((address.parse().ok()?, zone_id))
+}
+
+/// Parse a number of an IPv4 host in decimal, octal ("0" prefix) or hexadecimal
+/// ("0x" prefix) notation
+/// # Arguments
+/// * `part` - The number to parse
+/// # Returns
+/// * `Option<u64>` - The value, None if the part is not a number
+fn parse_ipv4_number(part: &str) -> Option<u64> {
+    let (digits, radix) = if let Some(digits) = part.strip_prefix("0x").or(part.strip_prefix("0X")) {
+        (digits, 16)
+    } else if part.len() > 1 && part.starts_with('0') {
+        (&part[1..], 8)
+    } else {
+        (part, 10)
+    };
+
+    if part.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    if digits.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(digits, radix).ok()
+}
+
+/// Parse an IPv4 host in any notation accepted by `inet_aton` and browsers, such as
+/// "2130706433", "0x7f.1" or "0177.0.0.1" which are all "127.0.0.1"
+/// # Arguments
+/// * `host` - The host to parse
+/// # Returns
+/// * `Option<Ipv4Addr>` - The address, None if the host is not an IPv4 address
+pub fn parse_ipv4_any_notation(host: &str) -> Option<Ipv4Addr> {
+    let host = host.strip_suffix('.').unwrap_or(host);
+    let mut numbers = host.split('.').map(parse_ipv4_number).collect::<Option<Vec<u64>>>()?;
+    if numbers.len() > 4 {
+        return None;
+    }
+
+    // The last number fills all the remaining bytes of the address
+    let last = numbers.pop()?;
+    if numbers.iter().any(|&number| number > 255) || last >= 1 << (8 * (4 - numbers.len())) {
+        return None;
+    }
+    let address = numbers.iter().enumerate()
+        .fold(last, |address, (index, &number)| address | number << (8 * (3 - index)));
+    Some(Ipv4Addr::from(address as u32))
+}
+
+/// Check if the given IPv4 address is reachable on the public internet
+/// # Arguments
+/// * `address` - The address to check
+/// # Returns
+/// * `bool` - False if the address is private, loopback, link-local, multicast,
+///   reserved or used for documentation, true otherwise
+pub fn is_public_ipv4(address: Ipv4Addr) -> bool {
+    let [a, b, c, _] = address.octets();
+    // https://www.iana.org/assignments/iana-ipv4-special-registry
+    !(a == 0
+        || a == 10
+        || (a == 100 && (64..128).contains(&b))
+        || a == 127
+        || (a == 169 && b == 254)
+        || (a == 172 && (16..32).contains(&b))
+        || (a == 192 && b == 0 && (c == 0 || c == 2))
+        || (a == 192 && b == 168)
+        || (a == 198 && (b == 18 || b == 19))
+        || (a == 198 && b == 51 && c == 100)
+        || (a == 203 && b == 0 && c == 113)
+        || a >= 224)
+}
+
+/// Check if the given IPv6 address is reachable on the public internet
+/// # Arguments
+/// * `address` - The address to check
+/// # Returns
+/// * `bool` - False if the address is loopback, unique local, link-local, multicast,
+///   reserved or embeds a non public IPv4 address, true otherwise
+pub fn is_public_ipv6(address: Ipv6Addr) -> bool {
+    let segments = address.segments();
+    let embedded_ipv4 = |high: u16, low: u16| Ipv4Addr::from((high as u32) << 16 | low as u32);
+
+    // https://www.iana.org/assignments/iana-ipv6-special-registry
+    match segments {
+        // IPv4-mapped and NAT64 addresses are checked as IPv4
+        [0, 0, 0, 0, 0, 0xffff, high, low] | [0x64, 0xff9b, 0, 0, 0, 0, high, low] =>
+            is_public_ipv4(embedded_ipv4(high, low)),
+        // 6to4 addresses embed the IPv4 address after the prefix
+        [0x2002, high, low, ..] => is_public_ipv4(embedded_ipv4(high, low)),
+        // Unspecified, loopback and deprecated IPv4-compatible addresses
+        [0, 0, 0, 0, 0, 0, _, _] => false,
+        [0x100, 0, 0, 0, ..] => false,
+        [0x2001, second, ..] if second < 0x200 || second == 0xdb8 => false,
+        [first, ..] => !((first & 0xfe00) == 0xfc00 || (first & 0xffc0) == 0xfe80
+            || (first & 0xffc0) == 0xfec0 || (first & 0xff00) == 0xff00),
+    }
+}
+
+/// Check if the given address is reachable on the public internet
+/// # Arguments
+/// * `address` - The address to check
+/// # Returns
+/// * `bool` - True if the address is public, false otherwise
+pub fn is_public_ip(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_public_ipv4(address),
+        IpAddr::V6(address) => is_public_ipv6(address),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use crate::validators::ip_helper::{parse_ipv4_host, parse_ipv6_host, parse_ipv4_any_notation,
+                                       is_public_ipv4, is_public_ipv6};
+
+    #[test]
+    fn parse_ipv4_host_classical() {
+        // Pass
+        assert_eq!(parse_ipv4_host("192.168.1.1"), Some(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(parse_ipv4_host("0.0.0.0"), Some(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(parse_ipv4_host("255.255.255.255"), Some(Ipv4Addr::BROADCAST));
+
+        // Fail
+        assert_eq!(parse_ipv4_host("256.1.1.1"), None);
+        assert_eq!(parse_ipv4_host("1.1.1"), None);
+        assert_eq!(parse_ipv4_host("1.1.1.1.1"), None);
+        assert_eq!(parse_ipv4_host("example.com"), None);
+
+        // Corner cases
+        // leading zeros could be read as octal so they are refused
+        assert_eq!(parse_ipv4_host("010.0.0.1"), None);
+        assert_eq!(parse_ipv4_host("1.1.1.1."), None);
+    }
+
+    #[test]
+    fn parse_ipv6_host_classical() {
+        // Pass
+        assert_eq!(parse_ipv6_host("[::1]"), Some((Ipv6Addr::LOCALHOST, None)));
+        assert_eq!(parse_ipv6_host("[2001:db8::8a2e:370:7334]"),
+                   Some(("2001:db8::8a2e:370:7334".parse().unwrap(), None)));
+        assert_eq!(parse_ipv6_host("[::ffff:192.0.2.1]"),
+                   Some((Ipv4Addr::new(192, 0, 2, 1).to_ipv6_mapped(), None)));
+
+        // Fail
+        assert_eq!(parse_ipv6_host("::1"), None);
+        assert_eq!(parse_ipv6_host("[::1"), None);
+        assert_eq!(parse_ipv6_host("[:::1]"), None);
+        assert_eq!(parse_ipv6_host("[192.168.1.1]"), None);
+    }
+
+    #[test]
+    fn parse_ipv6_host_zone_id() {
+        // Pass
+        assert_eq!(parse_ipv6_host("[fe80::1%25eth0]"),
+                   Some(("fe80::1".parse().unwrap(), Some("eth0".to_string()))));
+        assert_eq!(parse_ipv6_host("[fe80::1%25en%2D1]"),
+                   Some(("fe80::1".parse().unwrap(), Some("en-1".to_string()))));
+
+        // Fail & Corner cases
+        // the "%" separator must be encoded and the zone identifier can't be empty
+        assert_eq!(parse_ipv6_host("[fe80::1%eth0]"), None);
+        assert_eq!(parse_ipv6_host("[fe80::1%25]"), None);
+        assert_eq!(parse_ipv6_host("[fe80::1%25eth/0]"), None);
+    }
+
+    #[test]
+    fn parse_ipv4_any_notation_classical() {
+        let localhost = Some(Ipv4Addr::LOCALHOST);
+
+        // Pass
+        assert_eq!(parse_ipv4_any_notation("127.0.0.1"), localhost);
+        assert_eq!(parse_ipv4_any_notation("2130706433"), localhost);
+        assert_eq!(parse_ipv4_any_notation("0x7f000001"), localhost);
+        assert_eq!(parse_ipv4_any_notation("0177.0.0.1"), localhost);
+        assert_eq!(parse_ipv4_any_notation("0x7f.1"), localhost);
+        assert_eq!(parse_ipv4_any_notation("127.0.1"), localhost);
+        assert_eq!(parse_ipv4_any_notation("127.1."), localhost);
+
+        // Fail
+        assert_eq!(parse_ipv4_any_notation("example.com"), None);
+        assert_eq!(parse_ipv4_any_notation("1.2.3.4.5"), None);
+        assert_eq!(parse_ipv4_any_notation("256.0.0.1"), None);
+        assert_eq!(parse_ipv4_any_notation("4294967296"), None);
+        assert_eq!(parse_ipv4_any_notation("089.0.0.1"), None);
+        assert_eq!(parse_ipv4_any_notation("1..1"), None);
+        assert_eq!(parse_ipv4_any_notation("+1.0.0.1"), None);
+
+        // Corner cases
+        assert_eq!(parse_ipv4_any_notation("4294967295"), Some(Ipv4Addr::BROADCAST));
+        assert_eq!(parse_ipv4_any_notation("0x"), Some(Ipv4Addr::UNSPECIFIED));
+        assert_eq!(parse_ipv4_any_notation("1.2.3.0x100"), None);
+    }
+
+    #[test]
+    fn is_public_ipv4_classical() {
+        // Pass
+        assert!(is_public_ipv4(Ipv4Addr::new(8, 8, 8, 8)));
+        assert!(is_public_ipv4(Ipv4Addr::new(193, 134, 216, 1)));
+
+        // Fail
+        assert!(!is_public_ipv4(Ipv4Addr::new(127, 0, 0, 1)));
+        assert!(!is_public_ipv4(Ipv4Addr::new(10, 1, 2, 3)));
+        assert!(!is_public_ipv4(Ipv4Addr::new(192, 168, 1, 1)));
+        assert!(!is_public_ipv4(Ipv4Addr::new(169, 254, 169, 254)));
+        assert!(!is_public_ipv4(Ipv4Addr::new(224, 0, 0, 1)));
+        assert!(!is_public_ipv4(Ipv4Addr::new(255, 255, 255, 255)));
+        assert!(!is_public_ipv4(Ipv4Addr::new(0, 0, 0, 0)));
+
+        // Corner cases
+        // we test the bounds of the 172.16.0.0/12 and 100.64.0.0/10 ranges
+        assert!(is_public_ipv4(Ipv4Addr::new(172, 15, 255, 255)));
+        assert!(!is_public_ipv4(Ipv4Addr::new(172, 16, 0, 0)));
+        assert!(!is_public_ipv4(Ipv4Addr::new(172, 31, 255, 255)));
+        assert!(is_public_ipv4(Ipv4Addr::new(172, 32, 0, 0)));
+        assert!(is_public_ipv4(Ipv4Addr::new(100, 63, 255, 255)));
+        assert!(!is_public_ipv4(Ipv4Addr::new(100, 127, 255, 255)));
+        assert!(is_public_ipv4(Ipv4Addr::new(223, 255, 255, 255)));
+    }
+
+    #[test]
+    fn is_public_ipv6_classical() {
+        // Pass
+        assert!(is_public_ipv6("2606:4700:4700::1111".parse().unwrap()));
+        assert!(is_public_ipv6("::ffff:8.8.8.8".parse().unwrap()));
+
+        // Fail
+        assert!(!is_public_ipv6(Ipv6Addr::LOCALHOST));
+        assert!(!is_public_ipv6(Ipv6Addr::UNSPECIFIED));
+        assert!(!is_public_ipv6("fe80::1".parse().unwrap()));
+        assert!(!is_public_ipv6("fd00::1".parse().unwrap()));
+        assert!(!is_public_ipv6("ff02::1".parse().unwrap()));
+        assert!(!is_public_ipv6("2001:db8::1".parse().unwrap()));
+
+        // Corner cases
+        // IPv6 addresses embedding a private IPv4 address
+        assert!(!is_public_ipv6("::ffff:127.0.0.1".parse().unwrap()));
+        assert!(!is_public_ipv6("64:ff9b::10.0.0.1".parse().unwrap()));
+        assert!(!is_public_ipv6("2002:c0a8:0101::1".parse().unwrap()));
+        assert!(!is_public_ipv6("::127.0.0.1".parse().unwrap()));
+    }
+}
@@ -1,12 +1,63 @@
 mod validate_file;
+mod validate_png;
+mod validate_jpeg;
+mod validate_gif;
+mod validate_webp;
+mod validate_tiff;
+mod validate_iso_bmff;
+mod validate_avi;
+mod validate_asf;
+mod validate_matroska;
 mod validate_url;
+mod url_validator;
+mod normalize_url;
+mod resolve_url;
+mod find_urls;
 mod validate_uuid;
+mod validate_email;
+mod validate_hostname;
+mod url_options;
+mod email_options;
+mod public_suffix_list;
+mod host_list;
 mod error_messages;
+mod validation_error;
 mod file_helper;
+mod binary_helper;
+mod riff_helper;
+mod percent_encoding_helper;
+mod ip_helper;
+mod idn_helper;
+mod host_resolver;
 mod test_helper;
 
 pub use validate_file::*;
+pub use validate_png::*;
+pub use validate_jpeg::*;
+pub use validate_gif::*;
+pub use validate_webp::*;
+pub use validate_tiff::*;
+pub use validate_iso_bmff::*;
+pub use validate_avi::*;
+pub use validate_asf::*;
+pub use validate_matroska::*;
 pub use validate_url::*;
+pub use url_validator::*;
+pub use normalize_url::*;
+pub use resolve_url::*;
+pub use find_urls::*;
 pub use validate_uuid::*;
+pub use validate_email::*;
+pub use validate_hostname::*;
+pub use url_options::*;
+pub use email_options::*;
+pub use public_suffix_list::*;
+pub use host_list::*;
 pub use error_messages::*;
-pub use file_helper::*;
\ No newline at end of file
+pub use validation_error::*;
+pub use file_helper::*;
+pub use binary_helper::*;
+pub use percent_encoding_helper::*;
+pub use ip_helper::*;
+pub use idn_helper::*;
+pub use host_resolver::*;
@@ -0,0 +1,190 @@
+/// Give the value of an hexadecimal digit
+/// # Arguments
+/// * `digit` - The ascii hexadecimal digit
+/// # Returns
+/// * `Option<u8>` - The value of the digit, None if it is not an hexadecimal digit
+fn hex_value(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|value| value as u8)
+}
+
+/// Check if every "%" of the given input starts a valid percent-encoded octet
+/// # Arguments
+/// * `input` - The input to check
+/// # Returns
+/// * `bool` - True if the percent-encoding is valid, false otherwise
+pub fn is_valid_percent_encoding(input: &str) -> bool {
+    percent_decode(input).is_some()
+}
+
+/// Decode the percent-encoded octets of the given input
+/// # Arguments
+/// * `input` - The input to decode
+/// # Returns
+/// * `Option<Vec<u8>>` - The decoded bytes, None if an escape is malformed such as "%zz"
+pub fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let high = hex_value(*bytes.get(index + 1)?)?;
+            let low = hex_value(*bytes.get(index + 2)?)?;
+            decoded.push(high << 4 | low);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    Some(decoded)
+}
+
+/// Decode a percent-encoded input into an utf-8 string
+/// # Arguments
+/// * `input` - The input to decode
+/// # Returns
+/// * `Option<String>` - The decoded string, None if an escape is malformed or
+///   if the decoded bytes are not valid utf-8
+pub fn percent_decode_str(input: &str) -> Option<String> {
+    String::from_utf8(percent_decode(input)?).ok()
+}
+
+/// Check if the given byte is an unreserved character of RFC 3986 which never
+/// needs to be percent-encoded
+/// # Arguments
+/// * `byte` - The byte to check
+/// # Returns
+/// * `bool` - True if the byte is a letter, a digit, "-", ".", "_" or "~", false otherwise
+pub fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+/// Check if the given input has been percent-encoded twice, such as "%252F" which
+/// gives the escape "%2F" once decoded
+/// # Arguments
+/// * `input` - The input to check
+/// # Returns
+/// * `bool` - True if the decoded input still contains a valid escape, false otherwise
+pub fn is_double_encoded(input: &str) -> bool {
+    percent_decode(input).is_some_and(|decoded| decoded.windows(3).any(|window| {
+        window[0] == b'%' && hex_value(window[1]).is_some() && hex_value(window[2]).is_some()
+    }))
+}
+
+/// Percent-encode every byte of the input that is not kept
+/// # Arguments
+/// * `input` - The input to encode
+/// * `keep` - Returns true for the bytes that must not be encoded
+/// # Returns
+/// * `String` - The encoded input, using upper case hexadecimal digits
+pub fn percent_encode(input: &str, keep: fn(u8) -> bool) -> String {
+    input.bytes().map(|byte| {
+        if keep(byte) { (byte as char).to_string() } else { format!("%{:02X}", byte) }
+    }).collect()
+}
+
+/// Normalize the percent-encoding of the given input (RFC 3986 section 6.2.2):
+/// unreserved characters are decoded and the other escapes use upper case digits
+/// # Arguments
+/// * `input` - The input to normalize
+/// # Returns
+/// * `String` - The normalized input, malformed escapes are left unchanged
+pub fn normalize_percent_encoding(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut normalized = String::with_capacity(input.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escape = match (bytes.get(index + 1), bytes.get(index + 2)) {
+            (Some(&high), Some(&low)) if bytes[index] == b'%' => hex_value(high).zip(hex_value(low)),
+            _ => None,
+        };
+        match escape {
+            Some((high, low)) => {
+                let byte = high << 4 | low;
+                if is_unreserved(byte) {
+                    normalized.push(byte as char);
+                } else {
+                    normalized.push_str(&format!("%{:02X}", byte));
+                }
+                index += 3;
+            },
+            None => {
+                // Copy the whole character to keep multi-byte characters intact
+                let character = input[index..].chars().next().unwrap();
+                normalized.push(character);
+                index += character.len_utf8();
+            },
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::percent_encoding_helper::{is_valid_percent_encoding, percent_decode, percent_decode_str,
+                                                     percent_encode, normalize_percent_encoding, is_unreserved,
+                                                     is_double_encoded};
+
+    #[test]
+    fn percent_decode_classical() {
+        // Pass
+        assert_eq!(percent_decode("a%20b"), Some(b"a b".to_vec()));
+        assert_eq!(percent_decode("%2F%2f"), Some(b"//".to_vec()));
+        assert_eq!(percent_decode_str("caf%C3%A9"), Some("café".to_string()));
+        assert_eq!(percent_decode_str(""), Some(String::new()));
+
+        // Fail
+        assert_eq!(percent_decode("%zz"), None);
+        assert_eq!(percent_decode_str("%FF"), None);
+    }
+
+    #[test]
+    fn is_valid_percent_encoding_truncated() {
+        // Corner cases
+        // we test escapes cut at the end of the input
+        assert!(!is_valid_percent_encoding("%"));
+        assert!(!is_valid_percent_encoding("a%2"));
+        assert!(is_valid_percent_encoding("a%25"));
+        assert!(!is_valid_percent_encoding("%%41"));
+    }
+
+    #[test]
+    fn percent_encode_classical() {
+        // Pass
+        assert_eq!(percent_encode("a b@c", is_unreserved), "a%20b%40c");
+        assert_eq!(percent_encode("café", is_unreserved), "caf%C3%A9");
+        assert_eq!(percent_encode("a-b._~", is_unreserved), "a-b._~");
+    }
+
+    #[test]
+    fn normalize_percent_encoding_classical() {
+        // Pass
+        assert_eq!(normalize_percent_encoding("%7e%41%2f"), "~A%2F");
+        assert_eq!(normalize_percent_encoding("/a%2db/%c3%a9"), "/a-b/%C3%A9");
+        assert_eq!(normalize_percent_encoding("/café"), "/café");
+
+        // Corner cases
+        // malformed escapes are left unchanged
+        assert_eq!(normalize_percent_encoding("%zz%2"), "%zz%2");
+        assert_eq!(normalize_percent_encoding("%%41"), "%A");
+    }
+
+    #[test]
+    fn is_double_encoded_classical() {
+        // Pass
+        assert!(is_double_encoded("%252F"));
+        assert!(is_double_encoded("..%252e%252e/"));
+        assert!(is_double_encoded("%25%34%31"));
+
+        // Fail
+        assert!(!is_double_encoded("%2F"));
+        assert!(!is_double_encoded("100%25"));
+        assert!(!is_double_encoded("plain"));
+
+        // Corner cases
+        assert!(!is_double_encoded("%25zz"));
+        assert!(!is_double_encoded("%zz"));
+    }
+}
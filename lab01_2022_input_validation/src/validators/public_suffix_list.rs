@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+use std::fmt;
+use lazy_static::lazy_static;
+
+use crate::validators::file_helper::{read_from_path};
+use crate::validators::idn_helper::{host_to_ascii};
+use crate::validators::validation_error::{ValidationError};
+
+/// Snapshot of https://publicsuffix.org/list/public_suffix_list.dat from 2023-02-09
+static EMBEDDED_PUBLIC_SUFFIX_LIST: &str = include_str!("../../data/public_suffix_list.dat");
+
+lazy_static! {
+    static ref EMBEDDED: PublicSuffixList = PublicSuffixList::parse(EMBEDDED_PUBLIC_SUFFIX_LIST);
+}
+
+/// Rules of the Public Suffix List, stored in ASCII form
+/// https://github.com/publicsuffix/list/wiki/Format
+#[derive(Clone, PartialEq, Eq)]
+pub struct PublicSuffixList {
+    /// Rules such as "co.uk"
+    rules: HashSet<String>,
+    /// Wildcard rules such as "*.ck", stored without the "*." prefix
+    wildcards: HashSet<String>,
+    /// Exception rules such as "!www.ck", stored without the "!" prefix
+    exceptions: HashSet<String>,
+}
+
+impl fmt::Debug for PublicSuffixList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PublicSuffixList")
+            .field("rules", &self.rules.len())
+            .field("wildcards", &self.wildcards.len())
+            .field("exceptions", &self.exceptions.len())
+            .finish()
+    }
+}
+
+impl PublicSuffixList {
+    /// Parse the content of a Public Suffix List file
+    /// # Arguments
+    /// * `content` - The rules, one per line, "//" starting a comment
+    /// # Returns
+    /// * `PublicSuffixList` - The parsed list
+    pub fn parse(content: &str) -> PublicSuffixList {
+        let mut list = PublicSuffixList {
+            rules: HashSet::new(),
+            wildcards: HashSet::new(),
+            exceptions: HashSet::new(),
+        };
+
+        // Only the first word of a line is read, the rest is ignored
+        for rule in content.lines().filter_map(|line| line.split_whitespace().next()) {
+            if rule.starts_with("//") {
+                continue;
+            }
+            let (set, rule) = if let Some(rule) = rule.strip_prefix('!') {
+                (&mut list.exceptions, rule)
+            } else if let Some(rule) = rule.strip_prefix("*.") {
+                (&mut list.wildcards, rule)
+            } else {
+                (&mut list.rules, rule)
+            };
+            // Some rules are given in unicode while hosts are compared in ASCII form
+            if let Some(rule) = host_to_ascii(rule) {
+                set.insert(rule);
+            }
+        }
+        list
+    }
+
+    /// Load a Public Suffix List from a file, such as an updated list
+    /// # Arguments
+    /// * `file_path` - The path of the list
+    /// # Returns
+    /// * `PublicSuffixList` - The parsed list
+    /// # Errors
+    /// * `ValidationError` - If the file can't be read or contains no rule
+    pub fn from_file(file_path: &str) -> Result<PublicSuffixList, ValidationError> {
+        let content = String::from_utf8(read_from_path(file_path)?)
+            .map_err(|_| ValidationError::InvalidPublicSuffixList)?;
+        let list = PublicSuffixList::parse(&content);
+
+        if list.rules.is_empty() && list.wildcards.is_empty() {
+            return Err(ValidationError::InvalidPublicSuffixList);
+        }
+        Ok(list)
+    }
+
+    /// Give the snapshot of the Public Suffix List embedded in the library
+    /// # Returns
+    /// * `&PublicSuffixList` - The embedded list, parsed on first use
+    pub fn embedded() -> &'static PublicSuffixList {
+        &EMBEDDED
+    }
+
+    /// Find the public suffix of a host with the longest matching rule
+    /// # Arguments
+    /// * `labels` - The labels of the host in lower case ASCII form
+    /// # Returns
+    /// * `Option<usize>` - The index of the first label of the public suffix,
+    ///   None if no rule matches the host
+    fn find_public_suffix(&self, labels: &[&str]) -> Option<usize> {
+        for index in 0..labels.len() {
+            let suffix = labels[index..].join(".");
+            // An exception rule removes the leftmost label of the matching wildcard
+            if self.exceptions.contains(&suffix) {
+                return Some(index + 1);
+            }
+            if self.rules.contains(&suffix)
+                || (index + 1 < labels.len() && self.wildcards.contains(&labels[index + 1..].join("."))) {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// Give the public suffix of a host such as "co.uk" for "www.example.co.uk"
+    /// # Arguments
+    /// * `host` - The host in ASCII form
+    /// # Returns
+    /// * `Option<String>` - The public suffix, None if the top level domain is unknown
+    pub fn public_suffix(&self, host: &str) -> Option<String> {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        let labels: Vec<&str> = host.split('.').collect();
+        let index = self.find_public_suffix(&labels)?;
+        Some(labels[index..].join("."))
+    }
+
+    /// Check if the given domain is a known public suffix such as "com" or "co.uk"
+    /// # Arguments
+    /// * `domain` - The domain in ASCII form, without leading full stop
+    /// # Returns
+    /// * `bool` - True if the domain is a public suffix, false otherwise
+    pub fn is_public_suffix(&self, domain: &str) -> bool {
+        let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+        let labels: Vec<&str> = domain.split('.').collect();
+        self.find_public_suffix(&labels) == Some(0)
+    }
+
+    /// Give the registrable domain (eTLD+1) of a host such as "example.co.uk"
+    /// for "www.example.co.uk"
+    /// # Arguments
+    /// * `host` - The host in ASCII form
+    /// # Returns
+    /// * `Option<String>` - The registrable domain, None if the top level domain is
+    ///   unknown or if the host is a public suffix
+    pub fn registrable_domain(&self, host: &str) -> Option<String> {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        let labels: Vec<&str> = host.split('.').collect();
+        match self.find_public_suffix(&labels)? {
+            0 => None,
+            index => Some(labels[index - 1..].join(".")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+    use std::sync::Arc;
+
+    use crate::validators::public_suffix_list::{PublicSuffixList};
+    use crate::validators::url_options::{UrlOptions};
+    use crate::validators::validate_url::{parse_url_with_options};
+    use crate::validators::validation_error::{ValidationError, UrlComponent};
+    use crate::validators::test_helper::{BASE_FILE_PATH};
+
+    // Tests has been written with file example found here:
+    // https://file-examples.com/
+    static OTHERS_FOLDER: &str = "others/";
+    static NAMING_CONVENTION : &str = "file_example_";
+
+    #[test]
+    fn public_suffix_embedded() {
+        let list = PublicSuffixList::embedded();
+
+        // Pass
+        assert_eq!(list.public_suffix("www.example.com"), Some("com".to_string()));
+        assert_eq!(list.public_suffix("www.example.co.uk"), Some("co.uk".to_string()));
+        assert_eq!(list.public_suffix("example.xn--p1ai"), Some("xn--p1ai".to_string()));
+        assert!(list.is_public_suffix("co.uk"));
+        assert!(list.is_public_suffix("CH"));
+
+        // Fail
+        assert_eq!(list.public_suffix("example.notarealtld"), None);
+        assert!(!list.is_public_suffix("example.com"));
+        assert!(!list.is_public_suffix("notarealtld"));
+    }
+
+    #[test]
+    fn registrable_domain_embedded() {
+        let list = PublicSuffixList::embedded();
+
+        // Pass
+        assert_eq!(list.registrable_domain("www.example.co.uk"), Some("example.co.uk".to_string()));
+        assert_eq!(list.registrable_domain("Example.COM"), Some("example.com".to_string()));
+        assert_eq!(list.registrable_domain("a.b.example.ch."), Some("example.ch".to_string()));
+
+        // Fail
+        assert_eq!(list.registrable_domain("co.uk"), None);
+        assert_eq!(list.registrable_domain("example.notarealtld"), None);
+
+        // Corner cases
+        // private domains are public suffixes too
+        assert_eq!(list.registrable_domain("user.github.io"), Some("user.github.io".to_string()));
+    }
+
+    #[test]
+    fn public_suffix_wildcard_and_exception() {
+        let list = PublicSuffixList::parse("// comment\nck\n*.ck\n!www.ck\ncom ignored words\n");
+
+        // Pass
+        assert_eq!(list.public_suffix("a.example.ck"), Some("example.ck".to_string()));
+        assert_eq!(list.registrable_domain("a.example.ck"), Some("a.example.ck".to_string()));
+        assert!(list.is_public_suffix("com"));
+
+        // Corner cases
+        // the exception rule makes "www.ck" a registrable domain
+        assert_eq!(list.public_suffix("a.www.ck"), Some("ck".to_string()));
+        assert_eq!(list.registrable_domain("a.www.ck"), Some("www.ck".to_string()));
+        assert!(!list.is_public_suffix("www.ck"));
+        assert!(!list.is_public_suffix("// comment"));
+    }
+
+    #[test]
+    fn public_suffix_list_from_file() {
+        // Pass
+        let list = PublicSuffixList::from_file(
+            &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "psl.dat")).unwrap();
+        assert_eq!(list.public_suffix("www.heig.lab"), Some("lab".to_string()));
+        assert_eq!(list.public_suffix("a.b.sec.lab"), Some("b.sec.lab".to_string()));
+        assert_eq!(list.public_suffix("www.example.com"), None);
+        let options = UrlOptions {
+            public_suffix_list: Some(Arc::new(list)),
+            require_public_suffix: true,
+            ..UrlOptions::default()
+        };
+        let url = parse_url_with_options("https://www.heig.lab/", &options).unwrap();
+        assert_eq!(url.public_suffix, Some("lab".to_string()));
+        assert_eq!(url.registrable_domain, Some("heig.lab".to_string()));
+        assert_eq!(parse_url_with_options("https://www.example.com/", &options),
+                   Err(ValidationError::InvalidUrl(UrlComponent::TopLevelDomain)));
+
+        // Fail
+        assert_eq!(PublicSuffixList::from_file(
+            &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "docx.docx")),
+                   Err(ValidationError::InvalidPublicSuffixList));
+        assert_eq!(PublicSuffixList::from_file(
+            &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "test.test")),
+                   Err(ValidationError::ReadingFile(ErrorKind::NotFound)));
+    }
+}
@@ -0,0 +1,343 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::validators::validate_url::{ValidatedUrl, is_valid_scheme, parse_url_with_options, compile_url_options,
+                                      check_path_encoding, check_percent_encoding, parse_query};
+use crate::validators::normalize_url::{remove_dot_segments};
+use crate::validators::url_options::{UrlOptions};
+use crate::validators::validation_error::{ValidationError, UrlComponent};
+
+// https://www.rfc-editor.org/rfc/rfc3986#appendix-B
+static REGEX_URI_REFERENCE: &str = r"^(([^:/?#]+):)?(//([^/?#]*))?([^?#]*)(\?([^#]*))?(#(.*))?$";
+
+/// Components of an url reference, absolute or relative (RFC 3986 section 4.1)
+struct UrlReference<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl UrlReference<'_> {
+    /// Build the url from its components (RFC 3986 section 5.3)
+    /// # Returns
+    /// * `String` - The recomposed url
+    fn recompose(&self) -> String {
+        let mut url = String::new();
+        if let Some(scheme) = self.scheme {
+            url.push_str(scheme);
+            url.push(':');
+        }
+        if let Some(authority) = self.authority {
+            url.push_str("//");
+            url.push_str(authority);
+        }
+        url.push_str(self.path);
+        if let Some(query) = self.query {
+            url.push('?');
+            url.push_str(query);
+        }
+        if let Some(fragment) = self.fragment {
+            url.push('#');
+            url.push_str(fragment);
+        }
+        url
+    }
+}
+
+/// Split an url reference into its components
+/// # Arguments
+/// * `reference` - The reference to split
+/// # Returns
+/// * `UrlReference` - The components of the reference
+/// # Errors
+/// * `ValidationError` - If the reference starts with an invalid scheme, such as
+///   "1a:b" whose first segment can't contain a colon
+fn split_reference(reference: &str) -> Result<UrlReference<'_>, ValidationError> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(REGEX_URI_REFERENCE).unwrap();
+    }
+    // Every input matches the regex as all its groups are optional
+    let captures = RE.captures(reference).unwrap();
+    let component = |index: usize| captures.get(index).map(|component| component.as_str());
+
+    let scheme = component(2);
+    if scheme.is_some_and(|scheme| !is_valid_scheme(scheme)) {
+        return Err(ValidationError::InvalidUrl(UrlComponent::Scheme));
+    }
+    Ok(UrlReference {
+        scheme,
+        authority: component(4),
+        path: component(5).unwrap_or_default(),
+        query: component(7),
+        fragment: component(9),
+    })
+}
+
+/// Merge a relative path with the path of the base url (RFC 3986 section 5.2.3)
+/// # Arguments
+/// * `base` - The components of the base url
+/// * `path` - The relative path
+/// # Returns
+/// * `String` - The merged path
+fn merge_paths(base: &UrlReference, path: &str) -> String {
+    if base.authority.is_some() && base.path.is_empty() {
+        return format!("/{}", path);
+    }
+    match base.path.rfind('/') {
+        Some(index) => format!("{}{}", &base.path[..=index], path),
+        None => path.to_string(),
+    }
+}
+
+/// Resolve a reference against a base url and check the result with the given parser
+/// # Arguments
+/// * `base` - The absolute base url
+/// * `reference` - The reference to resolve
+/// * `parse` - The parser checking the base and the resolved url
+/// # Returns
+/// * `String` - The resolved url
+/// # Errors
+/// * `ValidationError` - If the base or the resolved url is not valid
+pub(crate) fn resolve_reference<F>(base: &str, reference: &str, parse: F) -> Result<String, ValidationError>
+    where F: Fn(&str) -> Result<ValidatedUrl, ValidationError> {
+    if parse(base)?.scheme.is_none() {
+        return Err(ValidationError::InvalidUrl(UrlComponent::Scheme));
+    }
+    let base = split_reference(base)?;
+    let reference = split_reference(reference)?;
+
+    // https://www.rfc-editor.org/rfc/rfc3986#section-5.2.2
+    let path;
+    let target = if reference.scheme.is_some() {
+        path = remove_dot_segments(reference.path);
+        UrlReference { path: &path, ..reference }
+    } else if reference.authority.is_some() {
+        path = remove_dot_segments(reference.path);
+        UrlReference { scheme: base.scheme, path: &path, ..reference }
+    } else if reference.path.is_empty() {
+        UrlReference {
+            scheme: base.scheme,
+            authority: base.authority,
+            path: base.path,
+            query: reference.query.or(base.query),
+            fragment: reference.fragment,
+        }
+    } else {
+        path = if reference.path.starts_with('/') {
+            remove_dot_segments(reference.path)
+        } else {
+            remove_dot_segments(&merge_paths(&base, reference.path))
+        };
+        UrlReference {
+            scheme: base.scheme,
+            authority: base.authority,
+            path: &path,
+            query: reference.query,
+            fragment: reference.fragment,
+        }
+    };
+
+    let resolved = target.recompose();
+    parse(&resolved)?;
+    Ok(resolved)
+}
+
+/// Check a given url reference, absolute or relative, with the given options.
+/// The scheme of a network-path reference such as "//cdn.example.com/x" is not checked
+/// as it is given by the base url
+/// # Arguments
+/// * `reference` - The reference to check
+/// * `options` - The options the reference must respect
+/// # Errors
+/// * `ValidationError` - If the options are not valid or `InvalidUrl` with the
+///   component that has been rejected
+fn check_url_reference(reference: &str, options: &UrlOptions) -> Result<(), ValidationError> {
+    compile_url_options(options)?;
+    let components = split_reference(reference)?;
+
+    if components.scheme.is_some() {
+        parse_url_with_options(reference, options)?;
+    } else if components.authority.is_some() {
+        let options = UrlOptions { require_scheme: false, ..options.clone() };
+        parse_url_with_options(&reference[2..], &options)?;
+    } else {
+        if !check_path_encoding(components.path, options)
+            || components.fragment.is_some_and(|fragment| !check_percent_encoding(fragment, options)) {
+            return Err(ValidationError::InvalidUrl(UrlComponent::Following));
+        }
+        if let Some(query) = components.query {
+            parse_query(query, options)?;
+        }
+    }
+    Ok(())
+}
+
+/// Validate a given url reference with the given options, such as "../img/a.png",
+/// "//cdn.example.com/x" or an absolute url
+/// # Arguments
+/// * `reference` - The reference to validate
+/// * `options` - The options the reference must respect
+/// # Return
+/// * `bool` - True if the reference is valid, false otherwise
+/// # Errors
+/// * `ValidationError` - If the options are not valid
+pub fn validate_url_reference_with_options(reference: &str, options: &UrlOptions) -> Result<bool, ValidationError> {
+    match check_url_reference(reference, options) {
+        Ok(()) => Ok(true),
+        Err(ValidationError::InvalidUrl(_)) => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+/// Validate a given url reference
+/// # Arguments
+/// * `reference` - The reference to validate
+/// * `top_level_domains_whitelist` - a list of top level domains
+/// # Return
+/// * `bool` - True if the reference is valid, false otherwise
+/// # Errors
+/// * `ValidationError` - If the whitelist is not valid
+pub fn validate_url_reference(reference: &str, top_level_domains_whitelist: Option<&Vec<&str>>) -> Result<bool, ValidationError> {
+    validate_url_reference_with_options(reference, &UrlOptions::from_whitelist(top_level_domains_whitelist))
+}
+
+/// Resolve a reference against an absolute base url (RFC 3986 section 5.2), the base
+/// and the resolved url must respect the given options
+/// # Arguments
+/// * `base` - The absolute base url such as "https://example.com/a/b"
+/// * `reference` - The reference to resolve such as "../img/a.png"
+/// * `options` - The options the urls must respect
+/// # Return
+/// * `String` - The resolved absolute url
+/// # Errors
+/// * `ValidationError` - If the options are not valid or `InvalidUrl` with the
+///   component of the base or of the resolved url that has been rejected
+pub fn resolve_url_with_options(base: &str, reference: &str, options: &UrlOptions) -> Result<String, ValidationError> {
+    resolve_reference(base, reference, |url| parse_url_with_options(url, options))
+}
+
+/// Resolve a reference against an absolute base url
+/// # Arguments
+/// * `base` - The absolute base url
+/// * `reference` - The reference to resolve
+/// * `top_level_domains_whitelist` - a list of top level domains
+/// # Return
+/// * `String` - The resolved absolute url
+/// # Errors
+/// * `ValidationError` - If the whitelist is not valid or `InvalidUrl` with the
+///   component that has been rejected
+pub fn resolve_url(base: &str, reference: &str, top_level_domains_whitelist: Option<&Vec<&str>>) -> Result<String, ValidationError> {
+    resolve_url_with_options(base, reference, &UrlOptions::from_whitelist(top_level_domains_whitelist))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::resolve_url::{resolve_url, resolve_url_with_options, validate_url_reference,
+                                         validate_url_reference_with_options};
+    use crate::validators::url_options::{UrlOptions};
+    use crate::validators::validation_error::{ValidationError, UrlComponent};
+    use crate::validators::test_helper::{result_helper};
+
+    static BASE: &str = "http://a.com/b/c/d;p?q";
+
+    #[test]
+    fn resolve_url_normal_examples() {
+        // Pass
+        // examples from RFC 3986 section 5.4.1
+        assert_eq!(resolve_url(BASE, "g", None).unwrap(), "http://a.com/b/c/g");
+        assert_eq!(resolve_url(BASE, "./g", None).unwrap(), "http://a.com/b/c/g");
+        assert_eq!(resolve_url(BASE, "g/", None).unwrap(), "http://a.com/b/c/g/");
+        assert_eq!(resolve_url(BASE, "/g", None).unwrap(), "http://a.com/g");
+        assert_eq!(resolve_url(BASE, "//g.com", None).unwrap(), "http://g.com");
+        assert_eq!(resolve_url(BASE, "?y", None).unwrap(), "http://a.com/b/c/d;p?y");
+        assert_eq!(resolve_url(BASE, "g?y", None).unwrap(), "http://a.com/b/c/g?y");
+        assert_eq!(resolve_url(BASE, "#s", None).unwrap(), "http://a.com/b/c/d;p?q#s");
+        assert_eq!(resolve_url(BASE, "g?y#s", None).unwrap(), "http://a.com/b/c/g?y#s");
+        assert_eq!(resolve_url(BASE, ";x", None).unwrap(), "http://a.com/b/c/;x");
+        assert_eq!(resolve_url(BASE, "", None).unwrap(), "http://a.com/b/c/d;p?q");
+        assert_eq!(resolve_url(BASE, ".", None).unwrap(), "http://a.com/b/c/");
+        assert_eq!(resolve_url(BASE, "..", None).unwrap(), "http://a.com/b/");
+        assert_eq!(resolve_url(BASE, "../g", None).unwrap(), "http://a.com/b/g");
+        assert_eq!(resolve_url(BASE, "../..", None).unwrap(), "http://a.com/");
+        assert_eq!(resolve_url(BASE, "../../g", None).unwrap(), "http://a.com/g");
+    }
+
+    #[test]
+    fn resolve_url_abnormal_examples() {
+        // Pass
+        // examples from RFC 3986 section 5.4.2
+        assert_eq!(resolve_url(BASE, "../../../g", None).unwrap(), "http://a.com/g");
+        assert_eq!(resolve_url(BASE, "/./g", None).unwrap(), "http://a.com/g");
+        assert_eq!(resolve_url(BASE, "/../g", None).unwrap(), "http://a.com/g");
+        assert_eq!(resolve_url(BASE, "g.", None).unwrap(), "http://a.com/b/c/g.");
+        assert_eq!(resolve_url(BASE, "..g", None).unwrap(), "http://a.com/b/c/..g");
+        assert_eq!(resolve_url(BASE, "./../g", None).unwrap(), "http://a.com/b/g");
+        assert_eq!(resolve_url(BASE, "g;x=1/../y", None).unwrap(), "http://a.com/b/c/y");
+        assert_eq!(resolve_url(BASE, "g#s/../x", None).unwrap(), "http://a.com/b/c/g#s/../x");
+        assert_eq!(resolve_url(BASE, "https://other.com/a/../b", None).unwrap(), "https://other.com/b");
+
+        // Corner cases
+        assert_eq!(resolve_url("https://example.com", "img/a.png", None).unwrap(), "https://example.com/img/a.png");
+        assert_eq!(resolve_url("https://example.com/a/", "../img/a.png", None).unwrap(), "https://example.com/img/a.png");
+    }
+
+    #[test]
+    fn resolve_url_policy() {
+        let options = UrlOptions {
+            top_level_domains_whitelist: Some(vec![".com".to_string()]),
+            schemes_whitelist: Some(vec!["https".to_string()]),
+            ..UrlOptions::default()
+        };
+
+        // Pass
+        assert_eq!(resolve_url_with_options("https://example.com/a/b", "//cdn.example.com/x", &options).unwrap(),
+                   "https://cdn.example.com/x");
+
+        // Fail
+        assert_eq!(resolve_url_with_options("https://example.com/", "//cdn.example.org/x", &options),
+                   Err(ValidationError::InvalidUrl(UrlComponent::TopLevelDomain)));
+        assert_eq!(resolve_url_with_options("https://example.com/", "http://example.com/", &options),
+                   Err(ValidationError::InvalidUrl(UrlComponent::Scheme)));
+        assert_eq!(resolve_url_with_options("https://example.com/", "g h", &options),
+                   Err(ValidationError::InvalidUrl(UrlComponent::Following)));
+        assert_eq!(resolve_url("example.com/a", "b", None),
+                   Err(ValidationError::InvalidUrl(UrlComponent::Scheme)));
+        assert!(resolve_url(BASE, "g:h", None).is_err());
+        assert_eq!(resolve_url(BASE, "g", Some(&vec!["com"])),
+                   Err(ValidationError::InvalidWhitelistTopLevelDomain(0)));
+    }
+
+    #[test]
+    fn validate_url_reference_classical() {
+        let options = UrlOptions {
+            require_scheme: true,
+            top_level_domains_whitelist: Some(vec![".com".to_string()]),
+            ..UrlOptions::default()
+        };
+
+        // Pass
+        result_helper(validate_url_reference("../img/a.png", None), true, None);
+        result_helper(validate_url_reference("/a/b?c=d#e", None), true, None);
+        result_helper(validate_url_reference("?page=2", None), true, None);
+        result_helper(validate_url_reference("#top", None), true, None);
+        result_helper(validate_url_reference("", None), true, None);
+        result_helper(validate_url_reference("https://example.com/", None), true, None);
+        result_helper(validate_url_reference_with_options("//cdn.example.com/x", &options), true, None);
+
+        // Fail
+        result_helper(validate_url_reference("../img/a b.png", None), false, None);
+        result_helper(validate_url_reference("a%zz", None), false, None);
+        result_helper(validate_url_reference("?a=%2", None), false, None);
+        result_helper(validate_url_reference("#a\u{7f}", None), false, None);
+        result_helper(validate_url_reference("1a:b", None), false, None);
+        result_helper(validate_url_reference_with_options("//cdn.example.org/x", &options), false, None);
+        result_helper(validate_url_reference("//", None), false, None);
+
+        // Corner cases
+        result_helper(validate_url_reference("./a:b", None), true, None);
+        result_helper(validate_url_reference("a", Some(&vec!["com"])), false,
+                      Some(ValidationError::InvalidWhitelistTopLevelDomain(0)));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::validators::binary_helper::{read_u32_le, four_cc_name};
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+// Identifier, size and form type starting a RIFF file
+const RIFF_HEADER_LENGTH: usize = 12;
+// Identifier and size preceding the data of a chunk
+const CHUNK_HEADER_LENGTH: usize = 8;
+
+/// Chunk of a RIFF file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RiffChunk<'a> {
+    /// The four characters code identifying the chunk
+    pub id: [u8; 4],
+    /// The offset of the chunk from the start of the file
+    pub offset: usize,
+    /// The data of the chunk, without its padding byte
+    pub data: &'a [u8],
+}
+
+impl RiffChunk<'_> {
+    /// Give the printable name of the chunk
+    /// # Returns
+    /// * `String` - The identifier of the chunk
+    pub fn name(&self) -> String {
+        four_cc_name(&self.id)
+    }
+
+    /// Create the error of the chunk
+    /// # Arguments
+    /// * `defect` - The rule broken by the chunk
+    /// # Returns
+    /// * `ValidationError` - The `InvalidFileStructure` error naming the chunk
+    pub fn error(&self, defect: StructureDefect) -> ValidationError {
+        InvalidStructure::error(&self.name(), self.offset, defect)
+    }
+}
+
+/// Check the header of a RIFF file, its size must match the length of the file
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `form_type` - The expected form type, such as "WEBP"
+/// # Return
+/// * `usize` - The offset of the first chunk, following the form type
+/// # Errors
+/// * `ValidationError` - If the header is not valid, if the file is shorter than the
+///   size announced or if data follows the RIFF chunk
+pub(crate) fn check_riff_header(buffer: &[u8], form_type: &[u8; 4]) -> Result<usize, ValidationError> {
+    if buffer.len() < RIFF_HEADER_LENGTH {
+        return Err(InvalidStructure::error("RIFF", 0, StructureDefect::Truncated));
+    }
+    if !buffer.starts_with(b"RIFF") || &buffer[8..12] != form_type {
+        return Err(InvalidStructure::error("RIFF", 0, StructureDefect::InvalidField));
+    }
+    let size = read_u32_le(buffer, 4).unwrap_or(0) as usize;
+    let end = CHUNK_HEADER_LENGTH + size;
+    if size < 4 {
+        return Err(InvalidStructure::error("RIFF", 0, StructureDefect::InvalidField));
+    }
+    if end > buffer.len() {
+        return Err(InvalidStructure::error("RIFF", 0, StructureDefect::Truncated));
+    }
+    if end < buffer.len() {
+        return Err(InvalidStructure::error("RIFF", end, StructureDefect::TrailingData));
+    }
+    Ok(RIFF_HEADER_LENGTH)
+}
+
+/// Read the chunks following each other between two offsets, every chunk being
+/// padded to an even length
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `start` - The offset of the first chunk
+/// * `end` - The offset following the last chunk, such as the end of the parent list
+/// # Return
+/// * `Vec<RiffChunk>` - The chunks in order of appearance
+/// # Errors
+/// * `ValidationError` - If a chunk ends after the given end
+pub(crate) fn read_riff_chunks(buffer: &[u8], start: usize, end: usize) -> Result<Vec<RiffChunk<'_>>, ValidationError> {
+    let mut chunks = Vec::new();
+    let mut offset = start;
+
+    while offset < end {
+        if end - offset < CHUNK_HEADER_LENGTH {
+            return Err(InvalidStructure::error("chunk", offset, StructureDefect::Truncated));
+        }
+        let id = [buffer[offset], buffer[offset + 1], buffer[offset + 2], buffer[offset + 3]];
+        let size = read_u32_le(buffer, offset + 4).unwrap_or(0) as usize;
+        let chunk = RiffChunk { id, offset, data: &[] };
+
+        let data_start = offset + CHUNK_HEADER_LENGTH;
+        if end - data_start < size + size % 2 {
+            return Err(chunk.error(StructureDefect::Truncated));
+        }
+        chunks.push(RiffChunk { data: &buffer[data_start..data_start + size], ..chunk });
+        offset = data_start + size + size % 2;
+    }
+    Ok(chunks)
+}
+
+/// Build a chunk padded to an even length, for the tests of the RIFF formats
+/// # Arguments
+/// * `id` - The identifier of the chunk
+/// * `data` - The data of the chunk
+/// # Returns
+/// * `Vec<u8>` - The chunk with its header and its padding
+#[cfg(test)]
+pub(crate) fn riff_chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = [&id[..], &(data.len() as u32).to_le_bytes(), data].concat();
+    if data.len() % 2 == 1 {
+        chunk.push(0);
+    }
+    chunk
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::riff_helper::{check_riff_header, read_riff_chunks};
+    use crate::validators::validation_error::{InvalidStructure, StructureDefect};
+
+    #[test]
+    fn check_riff_header_classical() {
+        // Pass
+        assert_eq!(check_riff_header(b"RIFF\x04\x00\x00\x00WEBP", b"WEBP"), Ok(12));
+
+        // Fail
+        assert_eq!(check_riff_header(b"RIFF\x04\x00\x00\x00WAVE", b"WEBP"),
+                   Err(InvalidStructure::error("RIFF", 0, StructureDefect::InvalidField)));
+        assert_eq!(check_riff_header(b"RIFF\x08\x00\x00\x00WEBP", b"WEBP"),
+                   Err(InvalidStructure::error("RIFF", 0, StructureDefect::Truncated)));
+        assert_eq!(check_riff_header(b"RIFF\x04\x00\x00\x00WEBPjunk", b"WEBP"),
+                   Err(InvalidStructure::error("RIFF", 12, StructureDefect::TrailingData)));
+
+        // Corner cases
+        assert_eq!(check_riff_header(b"RIFF", b"WEBP"), Err(InvalidStructure::error("RIFF", 0, StructureDefect::Truncated)));
+        assert_eq!(check_riff_header(b"RIFF\x00\x00\x00\x00WEBP", b"WEBP"),
+                   Err(InvalidStructure::error("RIFF", 0, StructureDefect::InvalidField)));
+    }
+
+    #[test]
+    fn read_riff_chunks_classical() {
+        let buffer = b"abcd\x01\x00\x00\x00x\x00efgh\x02\x00\x00\x00yz";
+
+        // Pass
+        let chunks = read_riff_chunks(buffer, 0, buffer.len()).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!((chunks[0].name(), chunks[0].offset, chunks[0].data), ("abcd".to_string(), 0, &b"x"[..]));
+        assert_eq!((chunks[1].name(), chunks[1].offset, chunks[1].data), ("efgh".to_string(), 10, &b"yz"[..]));
+
+        // Fail
+        assert_eq!(read_riff_chunks(buffer, 0, buffer.len() - 1),
+                   Err(InvalidStructure::error("efgh", 10, StructureDefect::Truncated)));
+        assert_eq!(read_riff_chunks(buffer, 0, 9), Err(InvalidStructure::error("abcd", 0, StructureDefect::Truncated)));
+
+        // Corner cases
+        assert_eq!(read_riff_chunks(buffer, 0, 4), Err(InvalidStructure::error("chunk", 0, StructureDefect::Truncated)));
+        assert_eq!(read_riff_chunks(buffer, 0, 0), Ok(vec![]));
+    }
+}
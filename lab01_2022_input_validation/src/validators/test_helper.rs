@@ -1,16 +1,35 @@
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+/// Folder containing the example files used by the tests, next to the crate
+#[allow(dead_code)]
+pub static BASE_FILE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../files/");
+
 /// Function that assert a Result to compare if it was the good one (error or value)
 /// # Arguments
 /// * `result` - the result to assert
 /// * `expected_value` - the expected value
 /// * `expected_error` - the expected error
 #[allow(dead_code)]
-pub fn result_helper(result: Result<bool, &str>, expected_value: bool, expected_error: Option<&str>) {
+pub fn result_helper(result: Result<bool, ValidationError>, expected_value: bool,
+                     expected_error: Option<ValidationError>) {
     match result {
         Ok(result) => assert_eq!(result, expected_value),
         Err(error) =>
             match expected_error {
-                Some(message) => assert_eq!(error, message),
-                None => assert!(false)
+                Some(expected) => assert_eq!(error, expected),
+                None => panic!("Unexpected error: {}", error)
             }
     }
-}
\ No newline at end of file
+}
+
+/// Function that gives the error expected when the structure of a file is invalid
+/// # Arguments
+/// * `part` - the part of the file that is invalid
+/// * `offset` - the offset of the part
+/// * `defect` - the defect of the part
+/// # Returns
+/// * `Result<(), ValidationError>` - the `InvalidFileStructure` error
+#[allow(dead_code)]
+pub fn invalid(part: &str, offset: usize, defect: StructureDefect) -> Result<(), ValidationError> {
+    Err(InvalidStructure::error(part, offset, defect))
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use crate::validators::public_suffix_list::{PublicSuffixList};
+use crate::validators::host_list::{HostList};
+use crate::validators::host_resolver::{HostResolver};
+
+/// Policy applied to hosts given as an IP address literal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IpHostPolicy {
+    /// IPv4 and IPv6 hosts are accepted
+    #[default]
+    Allow,
+    /// Only domain names are accepted as host
+    Deny,
+}
+
+/// Policy applied to the "user:password@" part of an url
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UserinfoPolicy {
+    /// Urls containing credentials are accepted
+    Allow,
+    /// Urls containing credentials are rejected, as they are used for phishing
+    /// such as "https://trusted.com@evil.com/"
+    #[default]
+    Deny,
+}
+
+/// Policy applied to internationalized hosts that could be a lookalike of another domain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HomographPolicy {
+    /// Lookalike hosts are accepted without check
+    Allow,
+    /// Lookalike hosts are accepted but marked as confusable in the result
+    #[default]
+    Flag,
+    /// Lookalike hosts are rejected
+    Deny,
+}
+
+/// Rules applied to the labels of a host
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HostnamePolicy {
+    /// Hosts must be valid DNS hostnames (RFC 1035 / RFC 1123)
+    #[default]
+    Strict,
+    /// Hosts may contain empty labels and labels starting or ending with a hyphen,
+    /// as accepted by the previous versions of `validate_url`
+    Lax,
+}
+
+/// Protection against Server-Side Request Forgery, for urls fetched by a server
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SsrfPolicy {
+    /// Every host is accepted
+    #[default]
+    Disabled,
+    /// Hosts that are private, loopback, link-local, multicast or reserved addresses,
+    /// in any IPv4 notation, and internal names such as "localhost" are rejected
+    Enabled,
+}
+
+/// Options used to validate an url
+#[derive(Debug, Clone, Default)]
+pub struct UrlOptions {
+    /// A list of accepted top level domains, every top level domain is accepted if None
+    pub top_level_domains_whitelist: Option<Vec<String>>,
+    /// A list of accepted schemes compared case-insensitively such as "https",
+    /// every scheme is accepted if None
+    pub schemes_whitelist: Option<Vec<String>>,
+    /// True if an url without scheme must be rejected
+    pub require_scheme: bool,
+    /// Maximum number of parameters in the query, unlimited if None
+    pub max_query_parameters: Option<usize>,
+    /// Maximum length of the query in bytes, unlimited if None
+    pub max_query_length: Option<usize>,
+    /// Rules applied to the labels of the host
+    pub hostname_policy: HostnamePolicy,
+    /// Policy applied to IPv4 and IPv6 hosts
+    pub ip_host_policy: IpHostPolicy,
+    /// A list of accepted ports, every port is accepted if None. An url without
+    /// port is always accepted
+    pub allowed_ports: Option<Vec<u16>>,
+    /// Policy applied to the user and password given in the url
+    pub userinfo_policy: UserinfoPolicy,
+    /// Policy applied to internationalized hosts mixing scripts or made of
+    /// characters confusable with ASCII
+    pub homograph_policy: HomographPolicy,
+    /// Public suffix list used instead of the embedded snapshot, such as an updated
+    /// list loaded with `PublicSuffixList::from_file`
+    pub public_suffix_list: Option<Arc<PublicSuffixList>>,
+    /// True if the host must end with a known public suffix, rejecting made-up
+    /// top level domains
+    pub require_public_suffix: bool,
+    /// A list of accepted registrable domains in ASCII form such as "example.co.uk",
+    /// every registrable domain is accepted if None, and ip addresses are rejected otherwise
+    pub registrable_domains_whitelist: Option<Vec<String>>,
+    /// Hosts accepted, every domain name is accepted if None. IP hosts never match
+    /// the list
+    pub allowed_hosts: Option<Arc<HostList>>,
+    /// Hosts rejected even if they are in the allowed hosts
+    pub denied_hosts: Option<Arc<HostList>>,
+    /// True if the path, query and fragment must not contain an encoded NUL "%00"
+    pub reject_encoded_nul: bool,
+    /// True if the path must not contain an encoded "/" or "\\", which could be decoded
+    /// after the path has been checked
+    pub reject_encoded_slash: bool,
+    /// True if the path must not contain encoded dot segments such as "%2e%2e"
+    pub reject_encoded_dot_segments: bool,
+    /// Protection against urls targeting the internal network
+    pub ssrf_policy: SsrfPolicy,
+    /// Resolver used when the SSRF protection is enabled to reject domain names
+    /// pointing to internal addresses, the names are not resolved if None
+    pub host_resolver: Option<Arc<dyn HostResolver>>,
+}
+
+impl UrlOptions {
+    /// Create options only restricting the top level domains
+    /// # Arguments
+    /// * `top_level_domains_whitelist` - a list of top level domains
+    /// # Returns
+    /// * `UrlOptions` - The options with the given whitelist and default values otherwise
+    pub fn from_whitelist(top_level_domains_whitelist: Option<&Vec<&str>>) -> UrlOptions {
+        UrlOptions {
+            top_level_domains_whitelist: top_level_domains_whitelist
+                .map(|domains| domains.iter().map(|&domain| domain.to_string()).collect()),
+            ..UrlOptions::default()
+        }
+    }
+}
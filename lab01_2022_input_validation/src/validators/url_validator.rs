@@ -0,0 +1,400 @@
+use std::sync::Arc;
+
+use crate::validators::validation_error::{ValidationError};
+use crate::validators::url_options::{UrlOptions, HostnamePolicy, IpHostPolicy, UserinfoPolicy, HomographPolicy,
+                                     SsrfPolicy};
+use crate::validators::validate_url::{ValidatedUrl, TopLevelDomainLabels, compile_url_options, parse_url_compiled};
+use crate::validators::normalize_url::{normalize_validated_url};
+use crate::validators::resolve_url::{resolve_reference};
+use crate::validators::public_suffix_list::{PublicSuffixList};
+use crate::validators::host_list::{HostList};
+use crate::validators::host_resolver::{HostResolver};
+
+/// Url validator whose options are checked and compiled once, to validate many urls
+/// without rebuilding the whitelists. It can be shared between threads
+#[derive(Debug, Clone)]
+pub struct UrlValidator {
+    options: UrlOptions,
+    top_level_domains: Option<TopLevelDomainLabels>,
+}
+
+impl UrlValidator {
+    /// Create a builder with the default options
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder of the validator
+    pub fn builder() -> UrlValidatorBuilder {
+        UrlValidatorBuilder::default()
+    }
+
+    /// Create a validator from the given options
+    /// # Arguments
+    /// * `options` - The options the urls must respect
+    /// # Returns
+    /// * `UrlValidator` - The compiled validator
+    /// # Errors
+    /// * `ValidationError` - If a whitelist of the options is not valid
+    pub fn new(options: UrlOptions) -> Result<UrlValidator, ValidationError> {
+        let top_level_domains = compile_url_options(&options)?;
+        Ok(UrlValidator { options, top_level_domains })
+    }
+
+    /// Give the options of the validator
+    /// # Returns
+    /// * `&UrlOptions` - The options the urls must respect
+    pub fn options(&self) -> &UrlOptions {
+        &self.options
+    }
+
+    /// Parse a given url into its components
+    /// # Arguments
+    /// * `url_input` - The url to parse
+    /// # Returns
+    /// * `ValidatedUrl` - The components of the url
+    /// # Errors
+    /// * `ValidationError` - `InvalidUrl` with the component that has been rejected
+    pub fn parse(&self, url_input: &str) -> Result<ValidatedUrl, ValidationError> {
+        parse_url_compiled(url_input, self.top_level_domains.as_deref(), &self.options)
+    }
+
+    /// Validate a given url
+    /// # Arguments
+    /// * `url_input` - The url to validate
+    /// # Returns
+    /// * `bool` - True if the url is valid, false otherwise
+    pub fn validate(&self, url_input: &str) -> bool {
+        self.parse(url_input).is_ok()
+    }
+
+    /// Give the canonical form of a given url
+    /// # Arguments
+    /// * `url_input` - The url to normalize
+    /// # Returns
+    /// * `String` - The normalized url
+    /// # Errors
+    /// * `ValidationError` - `InvalidUrl` with the component that has been rejected
+    pub fn normalize(&self, url_input: &str) -> Result<String, ValidationError> {
+        Ok(normalize_validated_url(&self.parse(url_input)?))
+    }
+
+    /// Resolve a reference against an absolute base url, the base and the resolved
+    /// url must be valid
+    /// # Arguments
+    /// * `base` - The absolute base url such as "https://example.com/a/b"
+    /// * `reference` - The reference to resolve such as "../img/a.png"
+    /// # Returns
+    /// * `String` - The resolved absolute url
+    /// # Errors
+    /// * `ValidationError` - `InvalidUrl` with the component that has been rejected
+    pub fn resolve(&self, base: &str, reference: &str) -> Result<String, ValidationError> {
+        resolve_reference(base, reference, |url| self.parse(url))
+    }
+}
+
+/// Builder of an `UrlValidator`, every option not given keeps its default value
+#[derive(Debug, Clone, Default)]
+pub struct UrlValidatorBuilder {
+    options: UrlOptions,
+}
+
+impl UrlValidatorBuilder {
+    /// Replace every option with the given ones
+    /// # Arguments
+    /// * `options` - The options replacing the current ones
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the given options
+    pub fn options(mut self, options: UrlOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Accept only the given top level domains such as ".com"
+    /// # Arguments
+    /// * `top_level_domains` - The accepted top level domains
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn top_level_domains_whitelist(mut self, top_level_domains: &[&str]) -> Self {
+        self.options.top_level_domains_whitelist = Some(to_strings(top_level_domains));
+        self
+    }
+
+    /// Accept only the given schemes, compared case-insensitively
+    /// # Arguments
+    /// * `schemes` - The accepted schemes
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn schemes_whitelist(mut self, schemes: &[&str]) -> Self {
+        self.options.schemes_whitelist = Some(to_strings(schemes));
+        self
+    }
+
+    /// Reject the urls without scheme if true
+    /// # Arguments
+    /// * `require_scheme` - True if the scheme is required
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn require_scheme(mut self, require_scheme: bool) -> Self {
+        self.options.require_scheme = require_scheme;
+        self
+    }
+
+    /// Limit the number of parameters in the query
+    /// # Arguments
+    /// * `max_query_parameters` - The maximum number of parameters
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn max_query_parameters(mut self, max_query_parameters: usize) -> Self {
+        self.options.max_query_parameters = Some(max_query_parameters);
+        self
+    }
+
+    /// Limit the length of the query in bytes
+    /// # Arguments
+    /// * `max_query_length` - The maximum length of the query
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn max_query_length(mut self, max_query_length: usize) -> Self {
+        self.options.max_query_length = Some(max_query_length);
+        self
+    }
+
+    /// Set the rules applied to the labels of the host
+    /// # Arguments
+    /// * `hostname_policy` - The policy applied to the labels
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn hostname_policy(mut self, hostname_policy: HostnamePolicy) -> Self {
+        self.options.hostname_policy = hostname_policy;
+        self
+    }
+
+    /// Set the policy applied to IPv4 and IPv6 hosts
+    /// # Arguments
+    /// * `ip_host_policy` - The policy applied to the IP hosts
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn ip_host_policy(mut self, ip_host_policy: IpHostPolicy) -> Self {
+        self.options.ip_host_policy = ip_host_policy;
+        self
+    }
+
+    /// Accept only the given ports, urls without port are always accepted
+    /// # Arguments
+    /// * `ports` - The accepted ports
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn allowed_ports(mut self, ports: &[u16]) -> Self {
+        self.options.allowed_ports = Some(ports.to_vec());
+        self
+    }
+
+    /// Set the policy applied to the user and password given in the url
+    /// # Arguments
+    /// * `userinfo_policy` - The policy applied to the userinfo
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn userinfo_policy(mut self, userinfo_policy: UserinfoPolicy) -> Self {
+        self.options.userinfo_policy = userinfo_policy;
+        self
+    }
+
+    /// Set the policy applied to internationalized lookalike hosts
+    /// # Arguments
+    /// * `homograph_policy` - The policy applied to the lookalike hosts
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn homograph_policy(mut self, homograph_policy: HomographPolicy) -> Self {
+        self.options.homograph_policy = homograph_policy;
+        self
+    }
+
+    /// Use the given public suffix list instead of the embedded snapshot
+    /// # Arguments
+    /// * `public_suffix_list` - The list to use
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn public_suffix_list(mut self, public_suffix_list: Arc<PublicSuffixList>) -> Self {
+        self.options.public_suffix_list = Some(public_suffix_list);
+        self
+    }
+
+    /// Reject the hosts without a known public suffix if true
+    /// # Arguments
+    /// * `require_public_suffix` - True if a known public suffix is required
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn require_public_suffix(mut self, require_public_suffix: bool) -> Self {
+        self.options.require_public_suffix = require_public_suffix;
+        self
+    }
+
+    /// Accept only the given registrable domains such as "example.co.uk"
+    /// # Arguments
+    /// * `registrable_domains` - The accepted registrable domains
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn registrable_domains_whitelist(mut self, registrable_domains: &[&str]) -> Self {
+        self.options.registrable_domains_whitelist = Some(to_strings(registrable_domains));
+        self
+    }
+
+    /// Accept only the hosts matching the given list
+    /// # Arguments
+    /// * `allowed_hosts` - The list of the allowed hosts
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn allowed_hosts(mut self, allowed_hosts: Arc<HostList>) -> Self {
+        self.options.allowed_hosts = Some(allowed_hosts);
+        self
+    }
+
+    /// Reject the hosts matching the given list, even if they are allowed
+    /// # Arguments
+    /// * `denied_hosts` - The list of the denied hosts
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn denied_hosts(mut self, denied_hosts: Arc<HostList>) -> Self {
+        self.options.denied_hosts = Some(denied_hosts);
+        self
+    }
+
+    /// Reject the encoded NULs in the path, query and fragment if true
+    /// # Arguments
+    /// * `reject_encoded_nul` - True if the encoded NULs are rejected
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn reject_encoded_nul(mut self, reject_encoded_nul: bool) -> Self {
+        self.options.reject_encoded_nul = reject_encoded_nul;
+        self
+    }
+
+    /// Reject the encoded slashes and backslashes in the path if true
+    /// # Arguments
+    /// * `reject_encoded_slash` - True if the encoded slashes are rejected
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn reject_encoded_slash(mut self, reject_encoded_slash: bool) -> Self {
+        self.options.reject_encoded_slash = reject_encoded_slash;
+        self
+    }
+
+    /// Reject the encoded dot segments in the path if true
+    /// # Arguments
+    /// * `reject_encoded_dot_segments` - True if the encoded dot segments are rejected
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn reject_encoded_dot_segments(mut self, reject_encoded_dot_segments: bool) -> Self {
+        self.options.reject_encoded_dot_segments = reject_encoded_dot_segments;
+        self
+    }
+
+    /// Set the protection against urls targeting the internal network
+    /// # Arguments
+    /// * `ssrf_policy` - The protection to apply
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn ssrf_policy(mut self, ssrf_policy: SsrfPolicy) -> Self {
+        self.options.ssrf_policy = ssrf_policy;
+        self
+    }
+
+    /// Resolve the domain names with the given resolver when the SSRF protection is enabled
+    /// # Arguments
+    /// * `host_resolver` - The resolver to use
+    /// # Returns
+    /// * `UrlValidatorBuilder` - The builder with the updated option
+    pub fn host_resolver(mut self, host_resolver: Arc<dyn HostResolver>) -> Self {
+        self.options.host_resolver = Some(host_resolver);
+        self
+    }
+
+    /// Check and compile the options
+    /// # Returns
+    /// * `UrlValidator` - The compiled validator
+    /// # Errors
+    /// * `ValidationError` - If a whitelist is not valid
+    pub fn build(self) -> Result<UrlValidator, ValidationError> {
+        UrlValidator::new(self.options)
+    }
+}
+
+/// Copy a list of string slices
+/// # Arguments
+/// * `values` - The values to copy
+/// # Returns
+/// * `Vec<String>` - The owned values
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|&value| value.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use crate::validators::url_validator::{UrlValidator};
+    use crate::validators::url_options::{UrlOptions, IpHostPolicy};
+    use crate::validators::validate_url::{validate_url_with_options};
+    use crate::validators::validation_error::{ValidationError, UrlComponent};
+
+    #[test]
+    fn url_validator_builder() {
+        let validator = UrlValidator::builder()
+            .top_level_domains_whitelist(&[".com", ".ch"])
+            .schemes_whitelist(&["https"])
+            .require_scheme(true)
+            .ip_host_policy(IpHostPolicy::Deny)
+            .build()
+            .unwrap();
+
+        // Pass
+        assert!(validator.validate("https://example.com/"));
+        assert!(validator.validate("HTTPS://www.heig-vd.ch/a?b=c"));
+        assert_eq!(validator.normalize("HTTPS://Example.com:443").unwrap(), "https://example.com/");
+        assert_eq!(validator.parse("https://www.example.com/").unwrap().subdomains, vec!["www".to_string()]);
+        assert_eq!(validator.resolve("https://example.com/a/b", "../c").unwrap(), "https://example.com/c");
+
+        // Fail
+        assert!(!validator.validate("http://example.com/"));
+        assert!(!validator.validate("example.com"));
+        assert!(!validator.validate("https://example.org/"));
+        assert!(validator.resolve("https://example.com/", "//example.org/").is_err());
+        assert_eq!(validator.parse("https://10.0.0.1/").err(),
+                   Some(ValidationError::InvalidUrl(UrlComponent::IpAddress)));
+        assert_eq!(UrlValidator::builder().top_level_domains_whitelist(&[".com", "c.m"]).build().err(),
+                   Some(ValidationError::InvalidWhitelistTopLevelDomain(1)));
+        assert_eq!(UrlValidator::builder().schemes_whitelist(&["1http"]).build().err(),
+                   Some(ValidationError::InvalidWhitelistScheme(0)));
+
+        // Corner cases
+        assert!(UrlValidator::builder().build().unwrap().validate("example.com"));
+    }
+
+    #[test]
+    fn url_validator_same_result_as_options() {
+        let options = UrlOptions::from_whitelist(Some(&vec![".com"]));
+        let validator = UrlValidator::new(options.clone()).unwrap();
+        let urls = ["https://example.com/", "example.ch", "http://a..com", "ftp://user@example.com", "https://example.com/%zz"];
+
+        // Pass
+        for url in urls {
+            assert_eq!(Ok(validator.validate(url)), validate_url_with_options(url, &options));
+        }
+    }
+
+    #[test]
+    fn url_validator_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<UrlValidator>();
+
+        let validator = Arc::new(UrlValidator::builder().top_level_domains_whitelist(&[".com"]).build().unwrap());
+        let handles: Vec<_> = (0..4).map(|index| {
+            let validator = Arc::clone(&validator);
+            thread::spawn(move || validator.validate(&format!("https://host{}.example.com/", index)))
+        }).collect();
+
+        // Pass
+        for handle in handles {
+            assert!(handle.join().unwrap());
+        }
+    }
+}
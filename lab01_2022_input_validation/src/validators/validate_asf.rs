@@ -0,0 +1,296 @@
+use crate::validators::binary_helper::{read_u32_le, read_u64_le};
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+/// Identifier of an ASF object, as stored in the file
+type Guid = [u8; 16];
+
+// Objects checked by the validator (ASF specification section 10.1)
+static HEADER_OBJECT: Guid = [0x30, 0x26, 0xb2, 0x75, 0x8e, 0x66, 0xcf, 0x11, 0xa6, 0xd9, 0x00, 0xaa, 0x00, 0x62, 0xce, 0x6c];
+static DATA_OBJECT: Guid = [0x36, 0x26, 0xb2, 0x75, 0x8e, 0x66, 0xcf, 0x11, 0xa6, 0xd9, 0x00, 0xaa, 0x00, 0x62, 0xce, 0x6c];
+static FILE_PROPERTIES_OBJECT: Guid = [0xa1, 0xdc, 0xab, 0x8c, 0x47, 0xa9, 0xcf, 0x11, 0x8e, 0xe4, 0x00, 0xc0, 0x0c, 0x20, 0x53, 0x65];
+static STREAM_PROPERTIES_OBJECT: Guid = [0x91, 0x07, 0xdc, 0xb7, 0xb7, 0xa9, 0xcf, 0x11, 0x8e, 0xe6, 0x00, 0xc0, 0x0c, 0x20, 0x53, 0x65];
+static HEADER_EXTENSION_OBJECT: Guid = [0xb5, 0x03, 0xbf, 0x5f, 0x2e, 0xa9, 0xcf, 0x11, 0x8e, 0xe3, 0x00, 0xc0, 0x0c, 0x20, 0x53, 0x65];
+static SIMPLE_INDEX_OBJECT: Guid = [0x90, 0x08, 0x00, 0x33, 0xb1, 0xe5, 0xcf, 0x11, 0x89, 0xf4, 0x00, 0xa0, 0xc9, 0x03, 0x49, 0xcb];
+
+// Identifier and size starting every object
+const OBJECT_HEADER_LENGTH: usize = 24;
+// Number of header objects and two reserved bytes following the header object size
+const HEADER_OBJECT_LENGTH: usize = 30;
+const HEADER_RESERVED: [u8; 2] = [0x01, 0x02];
+// Minimum sizes of the objects, including their object header
+const FILE_PROPERTIES_LENGTH: usize = 104;
+const STREAM_PROPERTIES_MIN_LENGTH: usize = 78;
+const HEADER_EXTENSION_MIN_LENGTH: usize = 46;
+const DATA_OBJECT_MIN_LENGTH: usize = 50;
+// Position of the file size and of the flags in the file properties object
+const FILE_SIZE_OFFSET: usize = 40;
+const FLAGS_OFFSET: usize = 88;
+// The file size is invalid while the file is being broadcast
+const BROADCAST_FLAG: u32 = 0x01;
+
+/// Object of an ASF file
+#[derive(Debug, Clone, Copy)]
+struct AsfObject {
+    guid: Guid,
+    offset: usize,
+    end: usize,
+}
+
+/// Give the name of an object, its GUID in registry format if it is not checked
+/// # Arguments
+/// * `guid` - The identifier of the object
+/// # Returns
+/// * `String` - The name of the object
+fn object_name(guid: &Guid) -> String {
+    let known = [(&HEADER_OBJECT, "Header Object"), (&DATA_OBJECT, "Data Object"),
+                 (&FILE_PROPERTIES_OBJECT, "File Properties Object"), (&STREAM_PROPERTIES_OBJECT, "Stream Properties Object"),
+                 (&HEADER_EXTENSION_OBJECT, "Header Extension Object"), (&SIMPLE_INDEX_OBJECT, "Simple Index Object")];
+    match known.iter().find(|(known, _)| *known == guid) {
+        Some((_, name)) => name.to_string(),
+        // The three first fields are little-endian
+        None => format!("{:08X}-{:04X}-{:04X}-{}-{}",
+                        u32::from_le_bytes([guid[0], guid[1], guid[2], guid[3]]),
+                        u16::from_le_bytes([guid[4], guid[5]]), u16::from_le_bytes([guid[6], guid[7]]),
+                        guid[8..10].iter().map(|byte| format!("{:02X}", byte)).collect::<String>(),
+                        guid[10..].iter().map(|byte| format!("{:02X}", byte)).collect::<String>()),
+    }
+}
+
+/// Read the header of the object at the given offset
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `offset` - The offset of the object
+/// * `parent_end` - The offset following the parent object, or the length of the file
+/// # Return
+/// * `AsfObject` - The object
+/// # Errors
+/// * `ValidationError` - If the header is truncated, the size is too small or the object
+///   exceeds its parent
+fn read_object(buffer: &[u8], offset: usize, parent_end: usize) -> Result<AsfObject, ValidationError> {
+    if parent_end - offset < OBJECT_HEADER_LENGTH {
+        return Err(InvalidStructure::error("object", offset, StructureDefect::Truncated));
+    }
+    let mut guid = [0; 16];
+    guid.copy_from_slice(&buffer[offset..offset + 16]);
+    let error = |defect| InvalidStructure::error(&object_name(&guid), offset, defect);
+
+    let size = read_u64_le(buffer, offset + 16).unwrap_or(0).try_into().unwrap_or(usize::MAX);
+    if size < OBJECT_HEADER_LENGTH {
+        return Err(error(StructureDefect::InvalidField));
+    }
+    if size > parent_end - offset {
+        return Err(error(StructureDefect::Truncated));
+    }
+    Ok(AsfObject { guid, offset, end: offset + size })
+}
+
+/// Check the objects of the header object
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `header` - The header object
+/// # Return
+/// * `AsfObject` - The file properties object
+/// # Errors
+/// * `ValidationError` - If an object is not valid, misplaced or missing, or if the
+///   number of objects does not match the header object
+fn check_header_objects(buffer: &[u8], header: &AsfObject) -> Result<AsfObject, ValidationError> {
+    let count = read_u32_le(buffer, OBJECT_HEADER_LENGTH).unwrap_or(0) as usize;
+    let mut objects = 0;
+    let mut file_properties = None;
+    let mut has_stream = false;
+
+    let mut offset = HEADER_OBJECT_LENGTH;
+    while offset < header.end {
+        let object = read_object(buffer, offset, header.end)?;
+        let length = object.end - object.offset;
+        let invalid = match object.guid {
+            guid if guid == FILE_PROPERTIES_OBJECT => {
+                if file_properties.is_some() {
+                    return Err(InvalidStructure::error(&object_name(&guid), offset, StructureDefect::Misplaced));
+                }
+                file_properties = Some(object);
+                length < FILE_PROPERTIES_LENGTH
+            },
+            guid if guid == STREAM_PROPERTIES_OBJECT => {
+                has_stream = true;
+                length < STREAM_PROPERTIES_MIN_LENGTH
+            },
+            guid if guid == HEADER_EXTENSION_OBJECT => length < HEADER_EXTENSION_MIN_LENGTH,
+            guid => guid == HEADER_OBJECT || guid == DATA_OBJECT,
+        };
+        if invalid {
+            return Err(InvalidStructure::error(&object_name(&object.guid), offset, StructureDefect::InvalidField));
+        }
+        objects += 1;
+        offset = object.end;
+    }
+
+    if objects != count {
+        return Err(InvalidStructure::error("Header Object", 0, StructureDefect::InvalidField));
+    }
+    if !has_stream {
+        return Err(InvalidStructure::error("Stream Properties Object", header.end, StructureDefect::Missing));
+    }
+    file_properties.ok_or_else(|| InvalidStructure::error("File Properties Object", header.end, StructureDefect::Missing))
+}
+
+/// Validate the structure of an ASF file such as WMV. The header object must come first
+/// with its file properties and stream properties objects, the data object must follow,
+/// every object must fit in its parent and the file size announced by the file
+/// properties must match the length of the file
+/// # Arguments
+/// * `buffer` - The content of the file
+/// # Return
+/// * `()` - If the structure is valid
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the first object that is not valid
+pub fn validate_asf(buffer: &[u8]) -> Result<(), ValidationError> {
+    let header = read_object(buffer, 0, buffer.len())?;
+    if header.guid != HEADER_OBJECT || header.end < HEADER_OBJECT_LENGTH || buffer[28..30] != HEADER_RESERVED {
+        return Err(InvalidStructure::error(&object_name(&header.guid), 0, StructureDefect::InvalidField));
+    }
+    let file_properties = check_header_objects(buffer, &header)?;
+
+    let flags = read_u32_le(buffer, file_properties.offset + FLAGS_OFFSET).unwrap_or(0);
+    let file_size = read_u64_le(buffer, file_properties.offset + FILE_SIZE_OFFSET).unwrap_or(0) as usize;
+    if flags & BROADCAST_FLAG == 0 && file_size != buffer.len() {
+        return Err(if file_size < buffer.len() {
+            InvalidStructure::error("File Properties Object", file_size, StructureDefect::TrailingData)
+        } else {
+            InvalidStructure::error("File Properties Object", file_properties.offset, StructureDefect::Truncated)
+        });
+    }
+
+    let mut offset = header.end;
+    while offset < buffer.len() {
+        let object = read_object(buffer, offset, buffer.len())?;
+        let is_data = object.guid == DATA_OBJECT;
+        if offset == header.end && !is_data {
+            return Err(InvalidStructure::error("Data Object", offset, StructureDefect::Missing));
+        }
+        if object.guid == HEADER_OBJECT || (is_data && offset != header.end) {
+            return Err(InvalidStructure::error(&object_name(&object.guid), offset, StructureDefect::Misplaced));
+        }
+        if is_data && object.end - object.offset < DATA_OBJECT_MIN_LENGTH {
+            return Err(InvalidStructure::error("Data Object", offset, StructureDefect::InvalidField));
+        }
+        offset = object.end;
+    }
+    if offset == header.end {
+        return Err(InvalidStructure::error("Data Object", offset, StructureDefect::Missing));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_asf::{validate_asf, object_name, Guid, HEADER_OBJECT, DATA_OBJECT,
+                                          FILE_PROPERTIES_OBJECT, STREAM_PROPERTIES_OBJECT, SIMPLE_INDEX_OBJECT};
+    use crate::validators::file_helper::{read_from_path};
+    use crate::validators::validation_error::{StructureDefect};
+    use crate::validators::test_helper::{invalid, BASE_FILE_PATH};
+
+    // Offsets of the objects of the fixture
+    const DATA_OFFSET: usize = 913;
+    const INDEX_OFFSET: usize = 1175363;
+
+    /// Build an object from its identifier and content
+    fn object(guid: &Guid, content: &[u8]) -> Vec<u8> {
+        [&guid[..], &(content.len() as u64 + 24).to_le_bytes(), content].concat()
+    }
+
+    /// Build an ASF file from its header objects and the objects following the data object
+    fn asf(header_objects: &[Vec<u8>], following: &[Vec<u8>]) -> Vec<u8> {
+        let header_content = [&(header_objects.len() as u32).to_le_bytes()[..], &[0x01, 0x02], &header_objects.concat()].concat();
+        let header = object(&HEADER_OBJECT, &header_content);
+        let data = object(&DATA_OBJECT, &[0; 26]);
+        let mut file = [header, data, following.concat()].concat();
+        // Update the file size of the file properties object
+        let file_size = (file.len() as u64).to_le_bytes();
+        if let Some(position) = file.windows(16).position(|window| window == FILE_PROPERTIES_OBJECT) {
+            file[position + 40..position + 48].copy_from_slice(&file_size);
+        }
+        file
+    }
+
+    /// Give the mandatory header objects
+    fn header_objects() -> Vec<Vec<u8>> {
+        vec![object(&FILE_PROPERTIES_OBJECT, &[0; 80]), object(&STREAM_PROPERTIES_OBJECT, &[0; 54])]
+    }
+
+    #[test]
+    fn object_name_classical() {
+        // Pass
+        assert_eq!(object_name(&HEADER_OBJECT), "Header Object");
+
+        // Corner cases
+        let mut unknown = HEADER_OBJECT;
+        unknown[0] = 0x31;
+        assert_eq!(object_name(&unknown), "75B22631-668E-11CF-A6D9-00AA0062CE6C");
+    }
+
+    #[test]
+    fn validate_asf_classical() {
+        let file = read_from_path(&format!("{}videos/file_example_wmv.wmv", BASE_FILE_PATH)).unwrap();
+
+        // Pass
+        assert_eq!(validate_asf(&file), Ok(()));
+        assert_eq!(validate_asf(&asf(&header_objects(), &[])), Ok(()));
+        assert_eq!(validate_asf(&asf(&header_objects(), &[object(&SIMPLE_INDEX_OBJECT, &[0; 32])])), Ok(()));
+
+        // Fail
+        let mut junk = file.clone();
+        junk.extend_from_slice(b"<?php echo 'test'; ?>");
+        assert_eq!(validate_asf(&junk), invalid("File Properties Object", file.len(), StructureDefect::TrailingData));
+        assert_eq!(validate_asf(&file[..INDEX_OFFSET]), invalid("File Properties Object", 30, StructureDefect::Truncated));
+        // File size consistent with a file truncated in the data object
+        let mut truncated = file[..DATA_OFFSET + 100].to_vec();
+        truncated[70..78].copy_from_slice(&(DATA_OFFSET as u64 + 100).to_le_bytes());
+        assert_eq!(validate_asf(&truncated), invalid("Data Object", DATA_OFFSET, StructureDefect::Truncated));
+
+        // Corner cases
+        assert_eq!(validate_asf(&[]), invalid("object", 0, StructureDefect::Truncated));
+        assert_eq!(validate_asf(&file[DATA_OFFSET..]), invalid("Data Object", 0, StructureDefect::InvalidField));
+        // Broadcast files have no file size
+        let mut broadcast = junk.clone();
+        broadcast[30 + 88] |= 0x01;
+        assert!(validate_asf(&broadcast).is_err());
+        broadcast.truncate(file.len());
+        assert_eq!(validate_asf(&broadcast), Ok(()));
+    }
+
+    #[test]
+    fn validate_asf_objects() {
+        let header_length = |objects: &[Vec<u8>]| 30 + objects.concat().len();
+
+        // Fail
+        let objects = &header_objects()[..1];
+        assert_eq!(validate_asf(&asf(objects, &[])), invalid("Stream Properties Object", header_length(objects), StructureDefect::Missing));
+        let objects = &header_objects()[1..];
+        assert_eq!(validate_asf(&asf(objects, &[])), invalid("File Properties Object", header_length(objects), StructureDefect::Missing));
+        let objects = [header_objects(), vec![object(&FILE_PROPERTIES_OBJECT, &[0; 80])]].concat();
+        assert_eq!(validate_asf(&asf(&objects, &[])), invalid("File Properties Object", 30 + 104 + 78, StructureDefect::Misplaced));
+        let objects = vec![object(&FILE_PROPERTIES_OBJECT, &[0; 79]), object(&STREAM_PROPERTIES_OBJECT, &[0; 54])];
+        assert_eq!(validate_asf(&asf(&objects, &[])), invalid("File Properties Object", 30, StructureDefect::InvalidField));
+        let objects = [header_objects(), vec![object(&DATA_OBJECT, &[0; 26])]].concat();
+        assert_eq!(validate_asf(&asf(&objects, &[])), invalid("Data Object", 30 + 104 + 78, StructureDefect::InvalidField));
+        let file = asf(&header_objects(), &[object(&DATA_OBJECT, &[0; 26])]);
+        assert_eq!(validate_asf(&file), invalid("Data Object", file.len() - 50, StructureDefect::Misplaced));
+
+        // Corner cases
+        // Number of header objects not matching
+        let mut file = asf(&header_objects(), &[]);
+        file[24] = 3;
+        assert_eq!(validate_asf(&file), invalid("Header Object", 0, StructureDefect::InvalidField));
+        // Object size smaller than its header and exceeding the header object
+        let mut file = asf(&header_objects(), &[]);
+        file[30 + 16] = 10;
+        assert_eq!(validate_asf(&file), invalid("File Properties Object", 30, StructureDefect::InvalidField));
+        let mut file = asf(&header_objects(), &[]);
+        file[30 + 104 + 16] = 0xff;
+        assert_eq!(validate_asf(&file), invalid("Stream Properties Object", 30 + 104, StructureDefect::Truncated));
+        let mut file = asf(&header_objects(), &[]);
+        file[29] = 0;
+        assert_eq!(validate_asf(&file), invalid("Header Object", 0, StructureDefect::InvalidField));
+    }
+}
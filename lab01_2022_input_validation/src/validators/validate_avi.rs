@@ -0,0 +1,305 @@
+use crate::validators::binary_helper::{read_u32_le, four_cc_name};
+use crate::validators::riff_helper::{check_riff_header, read_riff_chunks, RiffChunk};
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+// Size of the main AVI header and of a stream header (AVIMAINHEADER, AVISTREAMHEADER)
+const AVIH_MIN_LENGTH: usize = 56;
+const STRH_MIN_LENGTH: usize = 48;
+// Offsets of the flags and of the number of streams in the main AVI header
+const AVIH_FLAGS_OFFSET: usize = 12;
+const AVIH_STREAMS_OFFSET: usize = 24;
+// The file has an idx1 index
+const AVIF_HASINDEX: u32 = 0x10;
+// Identifier, flags, offset and size of an index entry
+const IDX1_ENTRY_LENGTH: usize = 16;
+// List type following the size of a list
+const LIST_TYPE_LENGTH: usize = 4;
+
+/// Give the type of a list chunk
+/// # Arguments
+/// * `chunk` - The chunk
+/// # Returns
+/// * `Option<[u8; 4]>` - The list type, None if the chunk is not a list
+fn list_type(chunk: &RiffChunk) -> Option<[u8; 4]> {
+    if &chunk.id != b"LIST" {
+        return None;
+    }
+    chunk.data.get(..LIST_TYPE_LENGTH).map(|list_type| [list_type[0], list_type[1], list_type[2], list_type[3]])
+}
+
+/// Create the error of a list, named by its type
+/// # Arguments
+/// * `chunk` - The list chunk
+/// * `defect` - The rule broken by the list
+/// # Returns
+/// * `ValidationError` - The `InvalidFileStructure` error naming the list
+fn list_error(chunk: &RiffChunk, defect: StructureDefect) -> ValidationError {
+    let name = list_type(chunk).map_or_else(|| chunk.name(), |list_type| format!("LIST {}", four_cc_name(&list_type)));
+    InvalidStructure::error(&name, chunk.offset, defect)
+}
+
+/// Read the chunks of a list
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `list` - The list chunk
+/// # Return
+/// * `Vec<RiffChunk>` - The chunks of the list
+/// # Errors
+/// * `ValidationError` - If the list has no type or a chunk exceeds the list
+fn read_list<'a>(buffer: &'a [u8], list: &RiffChunk) -> Result<Vec<RiffChunk<'a>>, ValidationError> {
+    if list.data.len() < LIST_TYPE_LENGTH {
+        return Err(list.error(StructureDefect::InvalidField));
+    }
+    let start = list.offset + 8 + LIST_TYPE_LENGTH;
+    read_riff_chunks(buffer, start, list.offset + 8 + list.data.len())
+}
+
+/// Check the header list, its main header and one stream list per stream
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `hdrl` - The "hdrl" list
+/// # Return
+/// * `bool` - True if the main header announces an idx1 index, false otherwise
+/// # Errors
+/// * `ValidationError` - If a header is missing, misplaced or too short
+fn check_header_list(buffer: &[u8], hdrl: &RiffChunk) -> Result<bool, ValidationError> {
+    let chunks = read_list(buffer, hdrl)?;
+    let avih = match chunks.first() {
+        Some(avih) if &avih.id == b"avih" => avih,
+        _ => return Err(InvalidStructure::error("avih", hdrl.offset, StructureDefect::Missing)),
+    };
+    if avih.data.len() < AVIH_MIN_LENGTH {
+        return Err(avih.error(StructureDefect::InvalidField));
+    }
+    let flags = read_u32_le(avih.data, AVIH_FLAGS_OFFSET).unwrap_or(0);
+    let streams = read_u32_le(avih.data, AVIH_STREAMS_OFFSET).unwrap_or(0) as usize;
+
+    let mut stream_lists = 0;
+    for chunk in &chunks[1..] {
+        match (&chunk.id, list_type(chunk).as_ref()) {
+            (b"avih", _) => return Err(chunk.error(StructureDefect::Misplaced)),
+            (_, Some(b"strl")) => {
+                let stream = read_list(buffer, chunk)?;
+                match stream.first() {
+                    Some(strh) if &strh.id == b"strh" && strh.data.len() < STRH_MIN_LENGTH => {
+                        return Err(strh.error(StructureDefect::InvalidField));
+                    },
+                    Some(strh) if &strh.id == b"strh" => {},
+                    _ => return Err(InvalidStructure::error("strh", chunk.offset, StructureDefect::Missing)),
+                }
+                if stream.get(1).is_none_or(|strf| &strf.id != b"strf") {
+                    return Err(InvalidStructure::error("strf", chunk.offset, StructureDefect::Missing));
+                }
+                stream_lists += 1;
+            },
+            (_, Some(_)) | (b"LIST", None) => { read_list(buffer, chunk)?; },
+            _ => {},
+        }
+    }
+    if streams == 0 || stream_lists != streams {
+        return Err(avih.error(StructureDefect::InvalidField));
+    }
+    Ok(flags & AVIF_HASINDEX != 0)
+}
+
+/// Check the data chunks of the movie list, grouped or not in "rec " lists
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `movi` - The "movi" list
+/// # Return
+/// * `()` - If every chunk is inside the list
+/// # Errors
+/// * `ValidationError` - If a chunk exceeds its list
+fn check_movie_list(buffer: &[u8], movi: &RiffChunk) -> Result<(), ValidationError> {
+    for chunk in read_list(buffer, movi)? {
+        match list_type(&chunk).as_ref() {
+            Some(b"rec ") => { read_list(buffer, &chunk)?; },
+            Some(_) => return Err(list_error(&chunk, StructureDefect::Misplaced)),
+            None if &chunk.id == b"LIST" => { read_list(buffer, &chunk)?; },
+            None => {},
+        }
+    }
+    Ok(())
+}
+
+/// Check that every entry of the index refers to a data chunk of the movie list. The
+/// offsets are relative to the type of the movie list, or to the start of the file
+/// when the first entry is beyond it
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `idx1` - The "idx1" chunk
+/// * `movi` - The "movi" list
+/// # Return
+/// * `()` - If every entry is valid
+/// # Errors
+/// * `ValidationError` - If an entry is outside of the movie list or does not match its chunk
+fn check_index(buffer: &[u8], idx1: &RiffChunk, movi: &RiffChunk) -> Result<(), ValidationError> {
+    if idx1.data.len() % IDX1_ENTRY_LENGTH != 0 {
+        return Err(idx1.error(StructureDefect::InvalidField));
+    }
+    let movi_type = movi.offset + 8;
+    let movi_end = movi_type + movi.data.len();
+    let first_offset = read_u32_le(idx1.data, 8).unwrap_or(0) as usize;
+    let base = if first_offset < movi_type { movi_type } else { 0 };
+
+    for (index, entry) in idx1.data.chunks_exact(IDX1_ENTRY_LENGTH).enumerate() {
+        let error = |defect| {
+            InvalidStructure::error(&format!("idx1 entry {}", index), idx1.offset + 8 + index * IDX1_ENTRY_LENGTH, defect)
+        };
+        let offset = base + read_u32_le(entry, 8).unwrap_or(0) as usize;
+        let size = read_u32_le(entry, 12).unwrap_or(0) as usize;
+        if offset < movi_type + LIST_TYPE_LENGTH || offset.checked_add(8 + size).is_none_or(|end| end > movi_end) {
+            return Err(error(StructureDefect::Truncated));
+        }
+        if buffer[offset..offset + 4] != entry[..4] || read_u32_le(buffer, offset + 4) != Some(size as u32) {
+            return Err(error(StructureDefect::InvalidField));
+        }
+    }
+    Ok(())
+}
+
+/// Validate the structure of an AVI file. The size of the RIFF header must match the
+/// length of the file, the "hdrl" list must come first with its main header and its
+/// stream headers, the "movi" list must follow and the "idx1" index, required when the
+/// main header announces it, must refer to the chunks of the "movi" list
+/// # Arguments
+/// * `buffer` - The content of the file
+/// # Return
+/// * `()` - If the structure is valid
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the first chunk that is not valid
+pub fn validate_avi(buffer: &[u8]) -> Result<(), ValidationError> {
+    let start = check_riff_header(buffer, b"AVI ")?;
+    let chunks = read_riff_chunks(buffer, start, buffer.len())?;
+
+    let hdrl = match chunks.first() {
+        Some(hdrl) if list_type(hdrl).as_ref() == Some(b"hdrl") => hdrl,
+        _ => return Err(InvalidStructure::error("LIST hdrl", start, StructureDefect::Missing)),
+    };
+    let has_index = check_header_list(buffer, hdrl)?;
+
+    let mut movi = None;
+    let mut idx1 = None;
+    for chunk in &chunks[1..] {
+        match (&chunk.id, list_type(chunk).as_ref()) {
+            (_, Some(b"hdrl")) => return Err(list_error(chunk, StructureDefect::Misplaced)),
+            (_, Some(b"movi")) if movi.is_some() => return Err(list_error(chunk, StructureDefect::Misplaced)),
+            (_, Some(b"movi")) => {
+                check_movie_list(buffer, chunk)?;
+                movi = Some(chunk);
+            },
+            (_, Some(_)) | (b"LIST", None) => { read_list(buffer, chunk)?; },
+            // The index follows the movie list it refers to
+            (b"idx1", _) if movi.is_none() || idx1.is_some() => return Err(chunk.error(StructureDefect::Misplaced)),
+            (b"idx1", _) => idx1 = Some(chunk),
+            _ => {},
+        }
+    }
+
+    let Some(movi) = movi else {
+        return Err(InvalidStructure::error("LIST movi", buffer.len(), StructureDefect::Missing));
+    };
+    match idx1 {
+        Some(idx1) => check_index(buffer, idx1, movi),
+        None if has_index => Err(InvalidStructure::error("idx1", buffer.len(), StructureDefect::Missing)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_avi::{validate_avi};
+    use crate::validators::file_helper::{read_from_path};
+    use crate::validators::riff_helper::{riff_chunk};
+    use crate::validators::validation_error::{StructureDefect};
+    use crate::validators::test_helper::{invalid, BASE_FILE_PATH};
+
+    // Offsets of the chunks of the fixture
+    const HDRL_OFFSET: usize = 12;
+    const MOVI_OFFSET: usize = 9972;
+    const IDX1_OFFSET: usize = 705094;
+
+    /// Build a list of the given type
+    fn list(list_type: &[u8; 4], chunks: &[Vec<u8>]) -> Vec<u8> {
+        riff_chunk(b"LIST", &[&list_type[..], &chunks.concat()].concat())
+    }
+
+    /// Build an AVI file from its chunks
+    fn avi(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let body = chunks.concat();
+        [&b"RIFF"[..], &(body.len() as u32 + 4).to_le_bytes(), b"AVI ", &body].concat()
+    }
+
+    /// Build a header list with the given flags and one stream
+    fn hdrl(flags: u32) -> Vec<u8> {
+        let mut avih = [0; 56];
+        avih[12..16].copy_from_slice(&flags.to_le_bytes());
+        avih[24] = 1;
+        list(b"hdrl", &[riff_chunk(b"avih", &avih), list(b"strl", &[riff_chunk(b"strh", &[0; 56]), riff_chunk(b"strf", &[0; 40])])])
+    }
+
+    #[test]
+    fn validate_avi_classical() {
+        let file = read_from_path(&format!("{}videos/file_example_avi.avi", BASE_FILE_PATH)).unwrap();
+
+        // Pass
+        assert_eq!(validate_avi(&file), Ok(()));
+        let movi = list(b"movi", &[riff_chunk(b"00dc", &[1, 2, 3]), list(b"rec ", &[riff_chunk(b"01wb", &[4])])]);
+        assert_eq!(validate_avi(&avi(&[hdrl(0), movi.clone()])), Ok(()));
+        // Index with offsets relative to the movie list
+        let index = [&b"00dc"[..], &[0; 4], &4u32.to_le_bytes(), &3u32.to_le_bytes()].concat();
+        assert_eq!(validate_avi(&avi(&[hdrl(0x10), movi.clone(), riff_chunk(b"idx1", &index)])), Ok(()));
+
+        // Fail
+        let mut junk = file.clone();
+        junk.extend_from_slice(b"<?php echo 'test'; ?>");
+        assert_eq!(validate_avi(&junk), invalid("RIFF", file.len(), StructureDefect::TrailingData));
+        // RIFF size consistent with a file truncated in the movie list
+        let mut truncated = file[..MOVI_OFFSET + 1000].to_vec();
+        truncated[4..8].copy_from_slice(&(MOVI_OFFSET as u32 + 992).to_le_bytes());
+        assert_eq!(validate_avi(&truncated), invalid("LIST", MOVI_OFFSET, StructureDefect::Truncated));
+        // Index entry outside of the movie list
+        let mut index = file.clone();
+        index[IDX1_OFFSET + 16..IDX1_OFFSET + 20].copy_from_slice(&0x00ff_ffffu32.to_le_bytes());
+        assert_eq!(validate_avi(&index), invalid("idx1 entry 0", IDX1_OFFSET + 8, StructureDefect::Truncated));
+
+        // Corner cases
+        assert_eq!(validate_avi(&avi(&[])), invalid("LIST hdrl", 12, StructureDefect::Missing));
+        assert_eq!(validate_avi(&avi(&[hdrl(0)])), invalid("LIST movi", 12 + hdrl(0).len(), StructureDefect::Missing));
+        let file = avi(&[hdrl(0x10), movi]);
+        assert_eq!(validate_avi(&file), invalid("idx1", file.len(), StructureDefect::Missing));
+        assert_eq!(validate_avi(&file[..HDRL_OFFSET]), invalid("RIFF", 0, StructureDefect::Truncated));
+        assert_eq!(validate_avi(&avi(&[hdrl(0), riff_chunk(b"LIST", &[0; 2])])), invalid("LIST", 12 + hdrl(0).len(), StructureDefect::InvalidField));
+    }
+
+    #[test]
+    fn validate_avi_lists() {
+        let movi = list(b"movi", &[riff_chunk(b"00dc", &[1, 2])]);
+        let movi_offset = 12 + hdrl(0).len();
+
+        // Fail
+        assert_eq!(validate_avi(&avi(&[movi.clone(), hdrl(0)])), invalid("LIST hdrl", 12, StructureDefect::Missing));
+        assert_eq!(validate_avi(&avi(&[hdrl(0), movi.clone(), movi.clone()])),
+                   invalid("LIST movi", movi_offset + movi.len(), StructureDefect::Misplaced));
+        assert_eq!(validate_avi(&avi(&[hdrl(0), riff_chunk(b"idx1", &[]), movi.clone()])), invalid("idx1", movi_offset, StructureDefect::Misplaced));
+        assert_eq!(validate_avi(&avi(&[hdrl(0), list(b"movi", &[list(b"hdrl", &[])])])),
+                   invalid("LIST hdrl", movi_offset + 12, StructureDefect::Misplaced));
+        // Header list without main header, with a short stream header or without stream format
+        let header = list(b"hdrl", &[list(b"strl", &[])]);
+        assert_eq!(validate_avi(&avi(&[header, movi.clone()])), invalid("avih", 12, StructureDefect::Missing));
+        let strh_offset = 12 + 12 + 64 + 12;
+        let header = list(b"hdrl", &[riff_chunk(b"avih", &{ let mut avih = [0; 56]; avih[24] = 1; avih }),
+                                     list(b"strl", &[riff_chunk(b"strh", &[0; 40]), riff_chunk(b"strf", &[0; 40])])]);
+        assert_eq!(validate_avi(&avi(&[header, movi.clone()])), invalid("strh", strh_offset, StructureDefect::InvalidField));
+
+        // Corner cases
+        // Number of streams not matching the stream lists
+        let header = list(b"hdrl", &[riff_chunk(b"avih", &[0; 56]), list(b"strl", &[riff_chunk(b"strh", &[0; 56]), riff_chunk(b"strf", &[])])]);
+        assert_eq!(validate_avi(&avi(&[header, movi.clone()])), invalid("avih", 24, StructureDefect::InvalidField));
+        let index = [&b"01wb"[..], &[0; 4], &4u32.to_le_bytes(), &2u32.to_le_bytes()].concat();
+        let idx1_offset = movi_offset + movi.len();
+        assert_eq!(validate_avi(&avi(&[hdrl(0), movi.clone(), riff_chunk(b"idx1", &index)])),
+                   invalid("idx1 entry 0", idx1_offset + 8, StructureDefect::InvalidField));
+        assert_eq!(validate_avi(&avi(&[hdrl(0), movi, riff_chunk(b"idx1", &index[..15])])), invalid("idx1", idx1_offset, StructureDefect::InvalidField));
+    }
+}
@@ -0,0 +1,296 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::validators::validation_error::{ValidationError, EmailComponent};
+use crate::validators::email_options::{EmailOptions, LocalPartPolicy};
+use crate::validators::url_options::{UrlOptions};
+use crate::validators::validate_url::{parse_url_with_options};
+
+// https://www.rfc-editor.org/rfc/rfc5322#section-3.2.3
+static REGEX_DOT_ATOM: &str = r"[a-zA-Z\d!#$%&'*+/=?^_`{|}~-]+(\.[a-zA-Z\d!#$%&'*+/=?^_`{|}~-]+)*";
+// https://www.rfc-editor.org/rfc/rfc5322#section-3.2.4, without folding white spaces
+static REGEX_QUOTED_STRING: &str = r#""([\x20\x21\x23-\x5b\x5d-\x7e]|\\[\x20-\x7e\t])*""#;
+// https://www.rfc-editor.org/rfc/rfc5321#section-4.5.3.1.1
+static MAX_LOCAL_PART_LENGTH: usize = 64;
+// https://www.rfc-editor.org/errata/eid1690
+static MAX_EMAIL_LENGTH: usize = 254;
+
+/// Email address split into its components by `parse_email`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedEmail {
+    /// The local part, before the "@", as given
+    pub local_part: String,
+    /// The domain in ASCII form
+    pub domain: String,
+    /// The domain in unicode form if it is an internationalized domain name
+    pub unicode_domain: Option<String>,
+    /// True if the internationalized domain could be a lookalike of another domain
+    pub confusable: bool,
+}
+
+/// Check if the given local part is valid
+/// # Arguments
+/// * `local_part` - The local part to check
+/// * `policy` - The syntax accepted for the local part
+/// # Returns
+/// * `bool` - True if the local part is a dot-atom or an accepted quoted string,
+///   false otherwise
+pub fn is_valid_local_part(local_part: &str, policy: LocalPartPolicy) -> bool {
+    lazy_static! {
+        static ref RE_DOT_ATOM: Regex = Regex::new(&format!("^{}$", REGEX_DOT_ATOM)).unwrap();
+        static ref RE_QUOTED_STRING: Regex = Regex::new(&format!("^{}$", REGEX_QUOTED_STRING)).unwrap();
+    }
+    RE_DOT_ATOM.is_match(local_part)
+        || (policy == LocalPartPolicy::AllowQuotedString && RE_QUOTED_STRING.is_match(local_part))
+}
+
+/// Parse a given email address into its components with the given options
+/// # Arguments
+/// * `email` - The email address to parse
+/// * `options` - The options the email address must respect
+/// # Return
+/// * `ValidatedEmail` - The components of the email address
+/// # Errors
+/// * `ValidationError` - If the whitelist is not valid or `InvalidEmail` with the
+///   component that has been rejected
+pub fn parse_email_with_options(email: &str, options: &EmailOptions) -> Result<ValidatedEmail, ValidationError> {
+    // A quoted local part may contain "@" while the domain can't
+    let (local_part, domain) = email.rsplit_once('@')
+        .ok_or(ValidationError::InvalidEmail(EmailComponent::Domain))?;
+
+    if local_part.len() > MAX_LOCAL_PART_LENGTH {
+        return Err(ValidationError::InvalidEmail(EmailComponent::Length));
+    }
+    if !is_valid_local_part(local_part, options.local_part_policy) {
+        return Err(ValidationError::InvalidEmail(EmailComponent::LocalPart));
+    }
+
+    // Only the host part of the url parser is used, address literals such as
+    // "[192.0.2.1]" are not accepted
+    if domain.is_empty() || domain.contains([':', '/', '?', '#', '[', ']', '%']) {
+        return Err(ValidationError::InvalidEmail(EmailComponent::Domain));
+    }
+    // The domain is given without a scheme, so the options about the scheme are ignored
+    let domain_options = UrlOptions {
+        require_scheme: false,
+        schemes_whitelist: None,
+        ..options.domain_options.clone()
+    };
+    let url = match parse_url_with_options(domain, &domain_options) {
+        Ok(url) if url.ip_address.is_none() => url,
+        Ok(_) | Err(ValidationError::InvalidUrl(_)) => return Err(ValidationError::InvalidEmail(EmailComponent::Domain)),
+        Err(error) => return Err(error),
+    };
+
+    let ascii_domain = url.host();
+    if local_part.len() + 1 + ascii_domain.len() > MAX_EMAIL_LENGTH {
+        return Err(ValidationError::InvalidEmail(EmailComponent::Length));
+    }
+
+    Ok(ValidatedEmail {
+        local_part: local_part.to_string(),
+        domain: ascii_domain,
+        unicode_domain: url.unicode_host,
+        confusable: url.confusable,
+    })
+}
+
+/// Parse a given email address into its components
+/// # Arguments
+/// * `email` - The email address to parse
+/// * `top_level_domains_whitelist` - a list of top level domains
+/// # Return
+/// * `ValidatedEmail` - The components of the email address
+/// # Errors
+/// * `ValidationError` - If the whitelist is not valid or `InvalidEmail` with the
+///   component that has been rejected
+pub fn parse_email(email: &str, top_level_domains_whitelist: Option<&Vec<&str>>) -> Result<ValidatedEmail, ValidationError> {
+    parse_email_with_options(email, &EmailOptions::from_whitelist(top_level_domains_whitelist))
+}
+
+/// Validate a given email address with the given options
+/// # Arguments
+/// * `email` - The email address to validate
+/// * `options` - The options the email address must respect
+/// # Return
+/// * `bool` - True if the email address is valid, false otherwise
+/// # Errors
+/// * `ValidationError` - If the options are not valid
+pub fn validate_email_with_options(email: &str, options: &EmailOptions) -> Result<bool, ValidationError> {
+    match parse_email_with_options(email, options) {
+        Ok(_) => Ok(true),
+        Err(ValidationError::InvalidEmail(_)) => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+/// Validate a given email address
+/// # Arguments
+/// * `email` - The email address to validate
+/// * `top_level_domains_whitelist` - a list of top level domains
+/// # Return
+/// * `bool` - True if the email address is valid, false otherwise
+/// # Errors
+/// * `ValidationError` - If the whitelist is not valid
+pub fn validate_email(email: &str, top_level_domains_whitelist: Option<&Vec<&str>>) -> Result<bool, ValidationError> {
+    validate_email_with_options(email, &EmailOptions::from_whitelist(top_level_domains_whitelist))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_email::{validate_email, validate_email_with_options, parse_email,
+                                            is_valid_local_part};
+    use crate::validators::email_options::{EmailOptions, LocalPartPolicy};
+    use crate::validators::url_options::{UrlOptions, HomographPolicy};
+    use crate::validators::validation_error::{ValidationError, EmailComponent};
+    use crate::validators::test_helper::{result_helper};
+
+    #[test]
+    fn is_valid_local_part_classical() {
+        // Pass
+        assert!(is_valid_local_part("simple", LocalPartPolicy::DotAtom));
+        assert!(is_valid_local_part("first.last+tag", LocalPartPolicy::DotAtom));
+        assert!(is_valid_local_part("name/surname", LocalPartPolicy::DotAtom));
+        assert!(is_valid_local_part("\"john..doe\"", LocalPartPolicy::AllowQuotedString));
+        assert!(is_valid_local_part("\" \"", LocalPartPolicy::AllowQuotedString));
+        assert!(is_valid_local_part("\"a\\\"b@c\"", LocalPartPolicy::AllowQuotedString));
+
+        // Fail
+        assert!(!is_valid_local_part("\"john..doe\"", LocalPartPolicy::DotAtom));
+        assert!(!is_valid_local_part("john..doe", LocalPartPolicy::AllowQuotedString));
+        assert!(!is_valid_local_part(".john", LocalPartPolicy::DotAtom));
+        assert!(!is_valid_local_part("john.", LocalPartPolicy::DotAtom));
+        assert!(!is_valid_local_part("a b", LocalPartPolicy::DotAtom));
+        assert!(!is_valid_local_part("a(b)", LocalPartPolicy::DotAtom));
+        assert!(!is_valid_local_part("jöhn", LocalPartPolicy::DotAtom));
+        assert!(!is_valid_local_part("\"a\"b\"", LocalPartPolicy::AllowQuotedString));
+        assert!(!is_valid_local_part("\"a\\\"", LocalPartPolicy::AllowQuotedString));
+
+        // Corner cases
+        assert!(!is_valid_local_part("", LocalPartPolicy::AllowQuotedString));
+        assert!(is_valid_local_part("\"\"", LocalPartPolicy::AllowQuotedString));
+    }
+
+    #[test]
+    fn validate_email_classical() {
+        // Pass
+        result_helper(validate_email("simple@example.com", None), true, None);
+        result_helper(validate_email("very.common@example.com", None), true, None);
+        result_helper(validate_email("user.name+tag+sorting@example.com", None), true, None);
+        result_helper(validate_email("x@mail.heig-vd.ch", None), true, None);
+
+        // Fail
+        result_helper(validate_email("Abc.example.com", None), false, None);
+        result_helper(validate_email("A@b@c@example.com", None), false, None);
+        result_helper(validate_email("john..doe@example.com", None), false, None);
+        result_helper(validate_email("user@", None), false, None);
+        result_helper(validate_email("@example.com", None), false, None);
+        result_helper(validate_email("user@example", None), false, None);
+        result_helper(validate_email("user@-example.com", None), false, None);
+        result_helper(validate_email("user@example.com:25", None), false, None);
+        result_helper(validate_email("user@example.com/path", None), false, None);
+        result_helper(validate_email("user@[192.0.2.1]", None), false, None);
+        result_helper(validate_email("user@192.0.2.1", None), false, None);
+        result_helper(validate_email("user@under_score.com", None), false, None);
+
+        // Corner cases
+        assert_eq!(parse_email("user@exa$mple.com", None), Err(ValidationError::InvalidEmail(EmailComponent::Domain)));
+        assert_eq!(parse_email("us er@example.com", None), Err(ValidationError::InvalidEmail(EmailComponent::LocalPart)));
+    }
+
+    #[test]
+    fn validate_email_quoted_string() {
+        let options = EmailOptions {
+            local_part_policy: LocalPartPolicy::AllowQuotedString,
+            ..EmailOptions::default()
+        };
+
+        // Pass
+        result_helper(validate_email_with_options("\"john..doe\"@example.org", &options), true, None);
+        result_helper(validate_email_with_options("\"a@b\"@example.org", &options), true, None);
+        result_helper(validate_email_with_options("simple@example.org", &options), true, None);
+
+        // Fail
+        result_helper(validate_email("\"john..doe\"@example.org", None), false, None);
+        result_helper(validate_email_with_options("just\"not\"right@example.com", &options), false, None);
+        result_helper(validate_email_with_options("this is\"not\\allowed@example.com", &options), false, None);
+    }
+
+    #[test]
+    fn validate_email_whitelist() {
+        let whitelist = vec![".ch", ".co.uk"];
+
+        // Pass
+        result_helper(validate_email("user@heig-vd.ch", Some(&whitelist)), true, None);
+        result_helper(validate_email("user@example.co.uk", Some(&whitelist)), true, None);
+
+        // Fail
+        result_helper(validate_email("user@example.com", Some(&whitelist)), false, None);
+        result_helper(validate_email("user@examplexch", Some(&whitelist)), false, None);
+
+        // Corner cases
+        result_helper(validate_email("user@heig-vd.ch", Some(&vec!["ch"])), false,
+                      Some(ValidationError::InvalidWhitelistTopLevelDomain(0)));
+    }
+
+    #[test]
+    fn validate_email_domain_options() {
+        let options = EmailOptions {
+            domain_options: UrlOptions {
+                require_scheme: true,
+                schemes_whitelist: Some(vec!["https".to_string()]),
+                ..UrlOptions::default()
+            },
+            ..EmailOptions::default()
+        };
+
+        // Pass
+        result_helper(validate_email_with_options("user@example.com", &options), true, None);
+        result_helper(validate_email_with_options("first.last@heig-vd.ch", &options), true, None);
+
+        // Fail
+        result_helper(validate_email_with_options("user@https://example.com", &options), false, None);
+        result_helper(validate_email_with_options("user@example", &options), false, None);
+    }
+
+    #[test]
+    fn parse_email_internationalized_domain() {
+        let options = EmailOptions {
+            domain_options: UrlOptions {
+                homograph_policy: HomographPolicy::Deny,
+                ..UrlOptions::default()
+            },
+            ..EmailOptions::default()
+        };
+
+        // Pass
+        let email = parse_email("user@münchen.de", None).unwrap();
+        assert_eq!(email.local_part, "user");
+        assert_eq!(email.domain, "xn--mnchen-3ya.de");
+        assert_eq!(email.unicode_domain, Some("münchen.de".to_string()));
+        assert!(!email.confusable);
+        assert!(parse_email("user@p\u{430}ypal.com", None).unwrap().confusable);
+
+        // Fail
+        result_helper(validate_email_with_options("user@p\u{430}ypal.com", &options), false, None);
+    }
+
+    #[test]
+    fn validate_email_length() {
+        let local_part = "a".repeat(64);
+        let domain = format!("{}.{}.{}.com", "b".repeat(63), "c".repeat(63), "d".repeat(57));
+
+        // Pass
+        result_helper(validate_email(&format!("{}@example.com", local_part), None), true, None);
+        assert_eq!(format!("{}@{}", local_part, domain).len(), 254);
+        result_helper(validate_email(&format!("{}@{}", local_part, domain), None), true, None);
+        result_helper(validate_email(&format!("{}@{}", "a".repeat(10), domain), None), true, None);
+
+        // Fail
+        assert_eq!(parse_email(&format!("a{}@example.com", local_part), None),
+                   Err(ValidationError::InvalidEmail(EmailComponent::Length)));
+        let long_domain = format!("{}.{}.{}.{}.com", "b".repeat(63), "c".repeat(63), "d".repeat(63), "e".repeat(4));
+        assert_eq!(parse_email(&format!("{}@{}", local_part, long_domain), None),
+                   Err(ValidationError::InvalidEmail(EmailComponent::Length)));
+    }
+}
@@ -1,7 +1,16 @@
 use infer::{is_image, is_video, get, Type};
 
-use crate::validators::error_messages::{INVALID_FILE_GROUP, INVALID_FILE_TYPE};
 use crate::validators::file_helper::{read_from_path};
+use crate::validators::validate_png::{validate_png};
+use crate::validators::validate_jpeg::{validate_jpeg};
+use crate::validators::validate_gif::{validate_gif};
+use crate::validators::validate_webp::{validate_webp};
+use crate::validators::validate_tiff::{validate_tiff};
+use crate::validators::validate_iso_bmff::{validate_iso_bmff};
+use crate::validators::validate_avi::{validate_avi};
+use crate::validators::validate_asf::{validate_asf};
+use crate::validators::validate_matroska::{validate_matroska};
+use crate::validators::validation_error::{ValidationError};
 
 /// Validates if the file has the same extension that his file type
 /// # Arguments
@@ -31,31 +40,62 @@ fn is_special_extension(file_path: &str, type_file: &Type) -> bool {
     }
 }
 
+/// Validate the internal structure of the file for the formats that can be walked,
+/// so that a file only starting with the right magic bytes is rejected
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `type_file` - The type of the file detected from its content
+/// * `extension` - The extension of the file in lowercase, None if it is not verified
+/// # Return
+/// * `()` - If the structure is valid or the format is not walked
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the part that is not valid
+fn validate_structure(buffer: &[u8], type_file: &Type, extension: Option<&str>) -> Result<(), ValidationError> {
+    match type_file.extension() {
+        "png" => validate_png(buffer),
+        "jpg" => validate_jpeg(buffer),
+        "gif" => validate_gif(buffer),
+        "webp" => validate_webp(buffer),
+        "tif" => validate_tiff(buffer),
+        "mp4" | "m4v" | "mov" => validate_iso_bmff(buffer, extension),
+        "avi" => validate_avi(buffer),
+        "wmv" => validate_asf(buffer),
+        "webm" | "mkv" => validate_matroska(buffer, extension),
+        _ => Ok(()),
+    }
+}
+
 /// Check if the given file path owns the valid content and extension
 /// # Arguments
 /// * `file_path` - The file path to check
-/// * `verify_extension` - True if the extension must be verified, false otherwise
+/// * `verify_extension` - True if the extension must be verified, false otherwise. The
+///   structure of the walked formats is verified in both cases, the extension of the MP4
+///   and MOV files being compared with their brands and the one of the WebM and Matroska
+///   files with their DocType only when verified
 /// # Returns
 /// `bool` True - if the file has the valid content and extension, false otherwise
 /// # Errors
-/// * `&str` - An error message if the file isn't a video or an image or if an other error occurs
-pub fn validate_file(file_path: &str, verify_extension: bool) -> Result<bool, &str> {
+/// * `ValidationError` - If the file isn't a video or an image, if its structure is
+///   not valid or if an other error occurs
+pub fn validate_file(file_path: &str, verify_extension: bool) -> Result<bool, ValidationError> {
     let buffer = read_from_path(file_path)?;
 
     // Verify the group of the file type
     if !is_video(&buffer) && !is_image(&buffer) {
-        return Err(INVALID_FILE_GROUP);
+        return Err(ValidationError::InvalidFileGroup);
     }
 
     // Verify the extension of the file if asked
     let file_type_buffer = get(&buffer);
     match file_type_buffer {
         Some(file_type) => {
+            let extension = file_path.trim().rsplit_once('.').map(|(_, extension)| extension.to_lowercase());
+            validate_structure(&buffer, &file_type, extension.as_deref().filter(|_| verify_extension))?;
             Ok(!verify_extension
                 || match_extension(file_path, &file_type)
                 || is_special_extension(file_path, &file_type))
         },
-        None => Err(INVALID_FILE_TYPE),
+        None => Err(ValidationError::InvalidFileType),
     }
 }
 
@@ -63,8 +103,9 @@ pub fn validate_file(file_path: &str, verify_extension: bool) -> Result<bool, &s
 #[cfg(test)]
 mod tests {
     use crate::validators::validate_file::{validate_file};
-    use crate::validators::test_helper::{result_helper};
-    use crate::validators::error_messages::{INVALID_FILE_GROUP, ERROR_READING_FILE};
+    use crate::validators::test_helper::{result_helper, BASE_FILE_PATH};
+    use crate::validators::validation_error::{ValidationError};
+    use std::io::ErrorKind;
 
     // Tests has been written with file example found here:
     // https://file-examples.com/
@@ -72,7 +113,6 @@ mod tests {
 
     // Modified folder contains files that has a content x and has an extension y
     // such as a jpg file has an php extension
-    static BASE_FILE_PATH : &str = "A:/HEIG/Semestre 6/SEC/Labos/SEC_Labo1/files/";
     static VIDEOS_FOLDER : &str = "videos/";
     static IMAGES_FOLDER : &str = "images/";
     static OTHERS_FOLDER: &str = "others/";
@@ -97,7 +137,7 @@ mod tests {
         // Other
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "csv.csv"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
     }
 
     #[test]
@@ -124,7 +164,7 @@ mod tests {
         // https://docs.rs/infer/0.7.0/infer/video/index.html
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, VIDEOS_FOLDER, NAMING_CONVENTION, "ogg.ogg"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
     }
 
     #[test]
@@ -177,7 +217,7 @@ mod tests {
         // https://docs.rs/infer/0.7.0/infer/image/index.html
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, IMAGES_FOLDER, NAMING_CONVENTION, "svg.svg"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
     }
 
     #[test]
@@ -219,19 +259,19 @@ mod tests {
         // Fail
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "csv.csv"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "docx.docx"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "pptx.pptx"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "txt.txt"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_FOLDER, NAMING_CONVENTION, "xlsx.xlsx"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
     }
 
     #[test]
@@ -239,19 +279,19 @@ mod tests {
         // Corner cases & Fail
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_MODIFIED_FOLDER, NAMING_CONVENTION, "csv.png"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_MODIFIED_FOLDER, NAMING_CONVENTION, "docx.jpg"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_MODIFIED_FOLDER, NAMING_CONVENTION, "pptx.mp4"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_MODIFIED_FOLDER, NAMING_CONVENTION, "txt.mov"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_MODIFIED_FOLDER, NAMING_CONVENTION, "xlsx.gif"),
-            true), false, Some(INVALID_FILE_GROUP));
+            true), false, Some(ValidationError::InvalidFileGroup));
     }
 
     #[test]
@@ -279,10 +319,10 @@ mod tests {
         // Others
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_MODIFIED_FOLDER, NAMING_CONVENTION, "csv.png"),
-            false), false, Some(INVALID_FILE_GROUP));
+            false), false, Some(ValidationError::InvalidFileGroup));
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, OTHERS_MODIFIED_FOLDER, NAMING_CONVENTION, "docx.jpg"),
-            false), false, Some(INVALID_FILE_GROUP));
+            false), false, Some(ValidationError::InvalidFileGroup));
     }
 
     #[test]
@@ -290,6 +330,6 @@ mod tests {
         // Corner Cases & Fail
         result_helper(validate_file(
             &format!("{}{}{}{}", BASE_FILE_PATH, IMAGES_MODIFIED_FOLDER, NAMING_CONVENTION, "test.test"),
-            false), false, Some(ERROR_READING_FILE));
+            false), false, Some(ValidationError::ReadingFile(ErrorKind::NotFound)));
     }
 }
@@ -0,0 +1,210 @@
+use crate::validators::binary_helper::{read_u16_le};
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+// Six bytes starting every GIF file, depending on the version
+static GIF_SIGNATURES: [&[u8; 6]; 2] = [b"GIF87a", b"GIF89a"];
+const LOGICAL_SCREEN_DESCRIPTOR_LENGTH: usize = 7;
+const IMAGE_DESCRIPTOR_LENGTH: usize = 10;
+// Introducers of the blocks following the logical screen descriptor
+const EXTENSION_INTRODUCER: u8 = 0x21;
+const IMAGE_SEPARATOR: u8 = 0x2c;
+const TRAILER: u8 = 0x3b;
+// Flag of the packed fields announcing a color table
+const COLOR_TABLE_FLAG: u8 = 0x80;
+
+/// Give the length of the color table announced by the packed fields of a descriptor
+/// # Arguments
+/// * `packed` - The packed fields of the logical screen or image descriptor
+/// # Returns
+/// * `usize` - The length of the color table in bytes, 0 if there is no table
+fn color_table_length(packed: u8) -> usize {
+    if packed & COLOR_TABLE_FLAG == 0 { 0 } else { 3 << ((packed & 0x07) + 1) }
+}
+
+/// Give the name and the size of the first sub-block of an extension
+/// # Arguments
+/// * `label` - The label following the extension introducer
+/// # Returns
+/// * `(String, Option<u8>)` - The name of the extension and the size of its first
+///   sub-block, None if the extension is unknown or has no fixed size
+fn extension_kind(label: u8) -> (String, Option<u8>) {
+    match label {
+        0x01 => ("plain text extension".to_string(), Some(12)),
+        0xf9 => ("graphic control extension".to_string(), Some(4)),
+        0xfe => ("comment extension".to_string(), None),
+        0xff => ("application extension".to_string(), Some(11)),
+        _ => (format!("extension 0x{:02x}", label), None),
+    }
+}
+
+/// Skip a sequence of data sub-blocks ended by a block terminator
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `offset` - The offset of the size of the first sub-block
+/// * `part` - The name of the block owning the sub-blocks
+/// * `start` - The offset of the block owning the sub-blocks
+/// # Return
+/// * `usize` - The offset following the block terminator
+/// # Errors
+/// * `ValidationError` - If a sub-block ends after the end of the file
+fn skip_sub_blocks(buffer: &[u8], mut offset: usize, part: &str, start: usize) -> Result<usize, ValidationError> {
+    loop {
+        let size = *buffer.get(offset)
+            .ok_or_else(|| InvalidStructure::error(part, start, StructureDefect::Truncated))? as usize;
+        offset += 1 + size;
+        if size == 0 {
+            return Ok(offset);
+        }
+        if offset > buffer.len() {
+            return Err(InvalidStructure::error(part, start, StructureDefect::Truncated));
+        }
+    }
+}
+
+/// Validate the structure of a GIF file by walking its blocks. The logical screen
+/// descriptor and the color tables must fit in the file, every extension and image
+/// must be complete and the trailer must end the file
+/// # Arguments
+/// * `buffer` - The content of the file
+/// # Return
+/// * `()` - If the structure is valid
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the first block that is not valid
+pub fn validate_gif(buffer: &[u8]) -> Result<(), ValidationError> {
+    if !GIF_SIGNATURES.iter().any(|signature| buffer.starts_with(*signature)) {
+        return Err(InvalidStructure::error("header", 0, StructureDefect::InvalidField));
+    }
+
+    let descriptor_offset = GIF_SIGNATURES[0].len();
+    let descriptor = buffer.get(descriptor_offset..descriptor_offset + LOGICAL_SCREEN_DESCRIPTOR_LENGTH)
+        .ok_or_else(|| InvalidStructure::error("logical screen descriptor", descriptor_offset, StructureDefect::Truncated))?;
+    if read_u16_le(descriptor, 0) == Some(0) || read_u16_le(descriptor, 2) == Some(0) {
+        return Err(InvalidStructure::error("logical screen descriptor", descriptor_offset, StructureDefect::InvalidField));
+    }
+    let mut offset = descriptor_offset + LOGICAL_SCREEN_DESCRIPTOR_LENGTH + color_table_length(descriptor[4]);
+    if offset > buffer.len() {
+        return Err(InvalidStructure::error("global color table", descriptor_offset + LOGICAL_SCREEN_DESCRIPTOR_LENGTH,
+                                           StructureDefect::Truncated));
+    }
+
+    let mut has_image = false;
+    while let Some(&introducer) = buffer.get(offset) {
+        match introducer {
+            IMAGE_SEPARATOR => {
+                let descriptor = buffer.get(offset..offset + IMAGE_DESCRIPTOR_LENGTH)
+                    .ok_or_else(|| InvalidStructure::error("image descriptor", offset, StructureDefect::Truncated))?;
+                if read_u16_le(descriptor, 5) == Some(0) || read_u16_le(descriptor, 7) == Some(0) {
+                    return Err(InvalidStructure::error("image descriptor", offset, StructureDefect::InvalidField));
+                }
+                let data_offset = offset + IMAGE_DESCRIPTOR_LENGTH + color_table_length(descriptor[9]);
+                // The image data starts with the minimum code size of the LZW compression
+                let code_size = *buffer.get(data_offset)
+                    .ok_or_else(|| InvalidStructure::error("image descriptor", offset, StructureDefect::Truncated))?;
+                if !(2..=8).contains(&code_size) {
+                    return Err(InvalidStructure::error("image data", data_offset, StructureDefect::InvalidField));
+                }
+                offset = skip_sub_blocks(buffer, data_offset + 1, "image data", data_offset)?;
+                has_image = true;
+            },
+            EXTENSION_INTRODUCER => {
+                let label = *buffer.get(offset + 1)
+                    .ok_or_else(|| InvalidStructure::error("extension", offset, StructureDefect::Truncated))?;
+                let (name, first_size) = extension_kind(label);
+                if let Some(size) = first_size {
+                    match buffer.get(offset + 2) {
+                        None => return Err(InvalidStructure::error(&name, offset, StructureDefect::Truncated)),
+                        Some(&actual) if actual != size => {
+                            return Err(InvalidStructure::error(&name, offset, StructureDefect::InvalidField));
+                        },
+                        _ => {},
+                    }
+                }
+                offset = skip_sub_blocks(buffer, offset + 2, &name, offset)?;
+            },
+            TRAILER => {
+                if !has_image {
+                    return Err(InvalidStructure::error("image descriptor", offset, StructureDefect::Missing));
+                }
+                if offset + 1 != buffer.len() {
+                    return Err(InvalidStructure::error("trailer", offset + 1, StructureDefect::TrailingData));
+                }
+                return Ok(());
+            },
+            _ => return Err(InvalidStructure::error(&format!("block 0x{:02x}", introducer), offset, StructureDefect::InvalidField)),
+        }
+    }
+
+    Err(InvalidStructure::error("trailer", buffer.len(), StructureDefect::Missing))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_gif::{validate_gif};
+    use crate::validators::file_helper::{read_from_path};
+    use crate::validators::validation_error::{StructureDefect};
+    use crate::validators::test_helper::{invalid, BASE_FILE_PATH};
+
+    // Header and logical screen descriptor of 1x1 pixel with a global color table of 2 colors
+    static HEADER: [u8; 19] = [b'G', b'I', b'F', b'8', b'9', b'a', 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00,
+                               0x00, 0x00, 0x00, 0xff, 0xff, 0xff];
+    static GRAPHIC_CONTROL: [u8; 8] = [0x21, 0xf9, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00];
+    // Image descriptor of 1x1 pixel followed by its image data
+    static IMAGE: [u8; 14] = [0x2c, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x01, 0x44, 0x00];
+    static TRAILER: [u8; 1] = [0x3b];
+
+    #[test]
+    fn validate_gif_classical() {
+        let file = read_from_path(&format!("{}images/file_example_gif.gif", BASE_FILE_PATH)).unwrap();
+
+        // Pass
+        assert_eq!(validate_gif(&file), Ok(()));
+        assert_eq!(validate_gif(&[&HEADER[..], &GRAPHIC_CONTROL, &IMAGE, &TRAILER].concat()), Ok(()));
+        let comment = [0x21, 0xfe, 0x02, b'h', b'i', 0x00];
+        let mut version_87 = HEADER;
+        version_87[4] = b'7';
+        assert_eq!(validate_gif(&[&version_87[..], &comment, &IMAGE, &IMAGE, &TRAILER].concat()), Ok(()));
+
+        // Fail
+        let mut junk = file.clone();
+        junk.extend_from_slice(b"<?php echo 'test'; ?>");
+        assert_eq!(validate_gif(&junk), invalid("trailer", file.len(), StructureDefect::TrailingData));
+        assert_eq!(validate_gif(&file[..file.len() - 1]), invalid("trailer", file.len() - 1, StructureDefect::Missing));
+        let image = HEADER.len() + GRAPHIC_CONTROL.len();
+        let file = [&HEADER[..], &GRAPHIC_CONTROL, &IMAGE, &TRAILER].concat();
+        assert_eq!(validate_gif(&file[..image + 12]), invalid("image data", image + 10, StructureDefect::Truncated));
+        assert_eq!(validate_gif(&file[..image + 5]), invalid("image descriptor", image, StructureDefect::Truncated));
+
+        // Corner cases
+        assert_eq!(validate_gif(b"GIF90a"), invalid("header", 0, StructureDefect::InvalidField));
+        assert_eq!(validate_gif(b"GIF89a\x01\x00"), invalid("logical screen descriptor", 6, StructureDefect::Truncated));
+        assert_eq!(validate_gif(&HEADER[..15]), invalid("global color table", 13, StructureDefect::Truncated));
+        assert_eq!(validate_gif(&HEADER), invalid("trailer", HEADER.len(), StructureDefect::Missing));
+    }
+
+    #[test]
+    fn validate_gif_blocks() {
+        let block = HEADER.len();
+
+        // Fail
+        assert_eq!(validate_gif(&[&HEADER[..], &TRAILER].concat()), invalid("image descriptor", block, StructureDefect::Missing));
+        assert_eq!(validate_gif(&[&HEADER[..], &[0x00], &IMAGE, &TRAILER].concat()), invalid("block 0x00", block, StructureDefect::InvalidField));
+        let mut graphic_control = GRAPHIC_CONTROL;
+        graphic_control[2] = 5;
+        assert_eq!(validate_gif(&[&HEADER[..], &graphic_control, &IMAGE, &TRAILER].concat()),
+                   invalid("graphic control extension", block, StructureDefect::InvalidField));
+        assert_eq!(validate_gif(&[&HEADER[..], &GRAPHIC_CONTROL[..5]].concat()),
+                   invalid("graphic control extension", block, StructureDefect::Truncated));
+        assert_eq!(validate_gif(&[&HEADER[..], &[0x21]].concat()), invalid("extension", block, StructureDefect::Truncated));
+
+        // Corner cases
+        let mut code_size = IMAGE;
+        code_size[10] = 12;
+        assert_eq!(validate_gif(&[&HEADER[..], &code_size, &TRAILER].concat()), invalid("image data", block + 10, StructureDefect::InvalidField));
+        let mut empty = IMAGE;
+        empty[5] = 0;
+        assert_eq!(validate_gif(&[&HEADER[..], &empty, &TRAILER].concat()), invalid("image descriptor", block, StructureDefect::InvalidField));
+        // Unknown extensions are skipped
+        let unknown = [0x21, 0x42, 0x01, 0x00, 0x00];
+        assert_eq!(validate_gif(&[&HEADER[..], &unknown, &IMAGE, &TRAILER].concat()), Ok(()));
+    }
+}
@@ -0,0 +1,69 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+// https://www.rfc-editor.org/rfc/rfc1123#section-2.1
+static REGEX_LABEL: &str = r"[a-zA-Z\d]([a-zA-Z\d-]{0,61}[a-zA-Z\d])?";
+static MAX_HOSTNAME_LENGTH: usize = 253;
+
+/// Validate if the given string is a valid DNS hostname (RFC 1035 / RFC 1123)
+/// # Arguments
+/// * `hostname` - The hostname in ASCII form to validate
+/// # Returns
+/// * `bool` - True if every label has 1 to 63 letters, digits or hyphens without
+///   leading or trailing hyphen and the hostname is at most 253 characters, false otherwise
+pub fn validate_hostname(hostname: &str) -> bool {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(&format!("^{}$", REGEX_LABEL)).unwrap();
+    }
+    hostname.len() <= MAX_HOSTNAME_LENGTH && hostname.split('.').all(|label| RE.is_match(label))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_hostname::{validate_hostname};
+
+    #[test]
+    fn validate_hostname_classical() {
+        // Pass
+        assert!(validate_hostname("example.com"));
+        assert!(validate_hostname("www.example.co.uk"));
+        assert!(validate_hostname("EXAMPLE.COM"));
+        assert!(validate_hostname("a-1.b2.com"));
+        assert!(validate_hostname("xn--mnchen-3ya.de"));
+        assert!(validate_hostname("localhost"));
+
+        // Fail
+        assert!(!validate_hostname(""));
+        assert!(!validate_hostname("....."));
+        assert!(!validate_hostname("----.com"));
+        assert!(!validate_hostname("exa_mple.com"));
+        assert!(!validate_hostname("exa mple.com"));
+    }
+
+    #[test]
+    fn validate_hostname_labels() {
+        // Corner cases
+        // we test empty labels and hyphens at the start or the end of a label
+        assert!(!validate_hostname(".example.com"));
+        assert!(!validate_hostname("example..com"));
+        assert!(!validate_hostname("example.com."));
+        assert!(!validate_hostname("-example.com"));
+        assert!(!validate_hostname("example-.com"));
+        assert!(!validate_hostname("example.-com"));
+        assert!(validate_hostname("e-x-a-m-p-l-e.com"));
+        assert!(validate_hostname("a.b"));
+    }
+
+    #[test]
+    fn validate_hostname_length() {
+        // Corner cases
+        // labels are limited to 63 characters
+        assert!(validate_hostname(&format!("{}.com", "a".repeat(63))));
+        assert!(!validate_hostname(&format!("{}.com", "a".repeat(64))));
+
+        // hostnames are limited to 253 characters
+        let label = "a".repeat(62);
+        assert!(validate_hostname(&format!("{0}.{0}.{0}.{0}.a", label)));
+        assert!(!validate_hostname(&format!("{0}.{0}.{0}.{0}.aa", label)));
+    }
+}
@@ -0,0 +1,299 @@
+use crate::validators::binary_helper::{read_u32_be, read_u64_be, four_cc_name};
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+/// Maximum nesting of container boxes, to bound the recursion on a crafted file
+pub const ISO_BMFF_MAX_DEPTH: usize = 16;
+
+const BOX_HEADER_LENGTH: usize = 8;
+// A size of 1 announces a 64 bits size following the type
+const LARGE_SIZE_LENGTH: usize = 8;
+// The "uuid" boxes carry an extended type following the size
+const EXTENDED_TYPE_LENGTH: usize = 16;
+// Major brand and minor version, followed by the compatible brands
+const FTYP_MIN_LENGTH: usize = 8;
+// Boxes containing only boxes (ISO/IEC 14496-12 section 4.2)
+static CONTAINER_BOXES: [&[u8; 4]; 11] = [b"moov", b"trak", b"edts", b"mdia", b"minf", b"dinf", b"stbl", b"mvex",
+                                          b"moof", b"traf", b"mfra"];
+// Top level boxes of the specification, the only ones accepted once the file is complete
+static TOP_LEVEL_BOXES: [&[u8; 4]; 17] = [b"ftyp", b"moov", b"mdat", b"free", b"skip", b"wide", b"uuid", b"meta", b"moof",
+                                         b"mfra", b"styp", b"sidx", b"ssix", b"prft", b"emsg", b"pdin", b"pnot"];
+// Brands of the MP4 family, including the ISO base brands
+static MP4_BRANDS: [&[u8; 4]; 16] = [b"isom", b"iso2", b"iso3", b"iso4", b"iso5", b"iso6", b"mp41", b"mp42",
+                                     b"avc1", b"dash", b"mmp4", b"MSNV", b"M4V ", b"M4A ", b"M4P ", b"f4v "];
+
+/// Box of an ISO base media file
+#[derive(Debug, Clone, Copy)]
+struct IsoBox {
+    box_type: [u8; 4],
+    offset: usize,
+    // Offset of the content, following the header
+    content: usize,
+    end: usize,
+}
+
+/// Read the header of the box at the given offset
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `offset` - The offset of the box
+/// * `parent_end` - The offset following the parent box, or the length of the file
+/// # Return
+/// * `IsoBox` - The box
+/// # Errors
+/// * `ValidationError` - If the header is truncated, the size is too small or the box
+///   exceeds its parent
+fn read_box(buffer: &[u8], offset: usize, parent_end: usize) -> Result<IsoBox, ValidationError> {
+    if parent_end - offset < BOX_HEADER_LENGTH {
+        return Err(InvalidStructure::error("box", offset, StructureDefect::Truncated));
+    }
+    let box_type = [buffer[offset + 4], buffer[offset + 5], buffer[offset + 6], buffer[offset + 7]];
+    let error = |defect| InvalidStructure::error(&four_cc_name(&box_type), offset, defect);
+
+    let mut header_length = BOX_HEADER_LENGTH;
+    let size = match read_u32_be(buffer, offset).unwrap_or(0) {
+        // The box extends to the end of its parent
+        0 => parent_end - offset,
+        1 => {
+            header_length += LARGE_SIZE_LENGTH;
+            read_u64_be(buffer, offset + BOX_HEADER_LENGTH)
+                .filter(|_| parent_end - offset >= header_length)
+                .ok_or_else(|| error(StructureDefect::Truncated))?
+                .try_into().unwrap_or(usize::MAX)
+        },
+        size => size as usize,
+    };
+    if &box_type == b"uuid" {
+        header_length += EXTENDED_TYPE_LENGTH;
+    }
+    if size < header_length {
+        return Err(error(StructureDefect::InvalidField));
+    }
+    if size > parent_end - offset {
+        return Err(error(StructureDefect::Truncated));
+    }
+    Ok(IsoBox { box_type, offset, content: offset + header_length, end: offset + size })
+}
+
+/// Check the boxes contained in a container box, recursively
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `parent` - The container box
+/// * `depth` - The nesting level of the container, 1 for a top level box
+/// # Return
+/// * `()` - If every box nests in its parent
+/// # Errors
+/// * `ValidationError` - If a box exceeds its parent or the nesting is too deep
+fn check_children(buffer: &[u8], parent: &IsoBox, depth: usize) -> Result<(), ValidationError> {
+    if depth > ISO_BMFF_MAX_DEPTH {
+        return Err(InvalidStructure::error(&four_cc_name(&parent.box_type), parent.offset, StructureDefect::LimitExceeded));
+    }
+    let mut offset = parent.content;
+    while offset < parent.end {
+        let child = read_box(buffer, offset, parent.end)?;
+        if CONTAINER_BOXES.contains(&&child.box_type) {
+            check_children(buffer, &child, depth + 1)?;
+        }
+        offset = child.end;
+    }
+    Ok(())
+}
+
+/// Check if a brand of the file is expected for the given extension
+/// # Arguments
+/// * `brand` - The major brand or a compatible brand of the "ftyp" box
+/// * `extension` - The extension of the file, in lowercase
+/// # Returns
+/// * `Option<bool>` - True if the brand matches the extension, None if the extension
+///   is not an ISO base media file extension
+fn brand_matches_extension(brand: &[u8; 4], extension: &str) -> Option<bool> {
+    match extension {
+        "mov" | "qt" => Some(brand == b"qt  "),
+        "mp4" | "m4v" | "m4a" | "m4p" => Some(MP4_BRANDS.contains(&brand)),
+        "3gp" => Some(brand.starts_with(b"3gp")),
+        "3g2" => Some(brand.starts_with(b"3g2")),
+        _ => None,
+    }
+}
+
+/// Check the "ftyp" box and its brands
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `ftyp` - The "ftyp" box
+/// * `extension` - The extension of the file, None if it is not verified
+/// # Return
+/// * `()` - If the box is valid and the major brand or a compatible brand matches the
+///   extension
+/// # Errors
+/// * `ValidationError` - If the box is not valid or no brand matches the extension
+fn check_file_type(buffer: &[u8], ftyp: &IsoBox, extension: Option<&str>) -> Result<(), ValidationError> {
+    let length = ftyp.end - ftyp.content;
+    if length < FTYP_MIN_LENGTH || length % 4 != 0 {
+        return Err(InvalidStructure::error("ftyp", ftyp.offset, StructureDefect::InvalidField));
+    }
+    // The major brand followed by the compatible brands, skipping the minor version
+    let brands: Vec<[u8; 4]> = (ftyp.content..ftyp.end).step_by(4)
+        .filter(|&offset| offset != ftyp.content + 4)
+        .map(|offset| [buffer[offset], buffer[offset + 1], buffer[offset + 2], buffer[offset + 3]])
+        .collect();
+    let rejected = extension.is_some_and(|extension| {
+        brands.iter().all(|brand| brand_matches_extension(brand, extension) == Some(false))
+    });
+    if rejected {
+        return Err(InvalidStructure::error(&format!("ftyp brand {}", four_cc_name(&brands[0])), ftyp.content,
+                                           StructureDefect::InvalidField));
+    }
+    Ok(())
+}
+
+/// Validate the structure of an ISO base media file such as MP4 or MOV. The file must
+/// start with a "ftyp" box whose major brand or a compatible brand matches the
+/// extension, contain a "moov" and a "mdat" box, and every box must nest in its parent
+/// up to `ISO_BMFF_MAX_DEPTH` levels. Once both boxes have been read, only the top
+/// level boxes of the specification can follow, so appended data forming such a box,
+/// e.g. a "free" box, cannot be told apart from the file and is accepted
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `extension` - The extension of the file to compare with the brands, None if it is
+///   not verified
+/// # Return
+/// * `()` - If the structure is valid
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the first box that is not valid
+pub fn validate_iso_bmff(buffer: &[u8], extension: Option<&str>) -> Result<(), ValidationError> {
+    let mut offset = 0;
+    let mut has_movie = false;
+    let mut has_media_data = false;
+
+    while offset < buffer.len() {
+        let top_box = read_box(buffer, offset, buffer.len());
+        // Once the file is complete, bytes not forming a known top level box have been appended
+        let known = top_box.as_ref().is_ok_and(|top_box| TOP_LEVEL_BOXES.contains(&&top_box.box_type));
+        if has_movie && has_media_data && !known {
+            return Err(InvalidStructure::error("box", offset, StructureDefect::TrailingData));
+        }
+        let top_box = top_box?;
+
+        match (&top_box.box_type, offset) {
+            (b"ftyp", 0) => check_file_type(buffer, &top_box, extension)?,
+            (b"ftyp", _) => return Err(InvalidStructure::error("ftyp", offset, StructureDefect::Misplaced)),
+            (_, 0) => return Err(InvalidStructure::error("ftyp", 0, StructureDefect::Missing)),
+            (b"moov", _) if has_movie => return Err(InvalidStructure::error("moov", offset, StructureDefect::Misplaced)),
+            (b"moov", _) => has_movie = true,
+            (b"mdat", _) => has_media_data = true,
+            _ => {},
+        }
+        if CONTAINER_BOXES.contains(&&top_box.box_type) {
+            check_children(buffer, &top_box, 1)?;
+        }
+        offset = top_box.end;
+    }
+
+    let missing = if offset == 0 { "ftyp" } else if !has_movie { "moov" } else if !has_media_data { "mdat" } else { return Ok(()) };
+    Err(InvalidStructure::error(missing, buffer.len(), StructureDefect::Missing))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_iso_bmff::{validate_iso_bmff, ISO_BMFF_MAX_DEPTH};
+    use crate::validators::file_helper::{read_from_path};
+    use crate::validators::validation_error::{StructureDefect};
+    use crate::validators::test_helper::{invalid, BASE_FILE_PATH};
+
+    /// Build a box from its type and content
+    fn iso_box(box_type: &[u8; 4], content: &[u8]) -> Vec<u8> {
+        [&(content.len() as u32 + 8).to_be_bytes()[..], box_type, content].concat()
+    }
+
+    /// Build a "ftyp" box with the given major brand
+    fn ftyp(brand: &[u8; 4]) -> Vec<u8> {
+        iso_box(b"ftyp", &[&brand[..], &[0; 4], b"isom"].concat())
+    }
+
+    /// Build a minimal "moov" box
+    fn moov() -> Vec<u8> {
+        iso_box(b"moov", &iso_box(b"mvhd", &[0; 4]))
+    }
+
+    #[test]
+    fn validate_iso_bmff_classical() {
+        let mp4 = read_from_path(&format!("{}videos/file_example_mp4.mp4", BASE_FILE_PATH)).unwrap();
+        let mov = read_from_path(&format!("{}videos/file_example_mov.mov", BASE_FILE_PATH)).unwrap();
+
+        // Pass
+        assert_eq!(validate_iso_bmff(&mp4, Some("mp4")), Ok(()));
+        assert_eq!(validate_iso_bmff(&mov, Some("mov")), Ok(()));
+        assert_eq!(validate_iso_bmff(&mov, None), Ok(()));
+        assert_eq!(validate_iso_bmff(&[ftyp(b"isom"), moov(), iso_box(b"mdat", &[1, 2, 3])].concat(), Some("m4v")), Ok(()));
+        // Major brand of a camera with the ISO base brand as compatible brand
+        assert_eq!(validate_iso_bmff(&[ftyp(b"XAVC"), moov(), iso_box(b"mdat", &[1, 2, 3])].concat(), Some("mp4")), Ok(()));
+
+        // Fail
+        // Data appended after the last box
+        let mut junk = mp4.clone();
+        junk.extend_from_slice(b"<?php echo 'test'; ?>");
+        assert_eq!(validate_iso_bmff(&junk, Some("mp4")), invalid("box", mp4.len(), StructureDefect::TrailingData));
+        let mut padding = mov.clone();
+        padding.extend_from_slice(&[0; 4]);
+        assert_eq!(validate_iso_bmff(&padding, Some("mov")), invalid("box", mov.len(), StructureDefect::TrailingData));
+        // Well-formed box of an unknown type and truncated known box appended
+        let mut unknown = mp4.clone();
+        unknown.extend_from_slice(&iso_box(b"junk", &[0; 4]));
+        assert_eq!(validate_iso_bmff(&unknown, Some("mp4")), invalid("box", mp4.len(), StructureDefect::TrailingData));
+        let mut truncated = mp4.clone();
+        truncated.extend_from_slice(&[&16u32.to_be_bytes()[..], b"moov"].concat());
+        assert_eq!(validate_iso_bmff(&truncated, Some("mp4")), invalid("box", mp4.len(), StructureDefect::TrailingData));
+        // Truncated in the media data
+        assert_eq!(validate_iso_bmff(&mp4[..mp4.len() - 1], Some("mp4")), invalid("mdat", 11864, StructureDefect::Truncated));
+        // Major brand not matching the extension
+        assert_eq!(validate_iso_bmff(&mov, Some("mp4")), invalid("ftyp brand qt  ", 8, StructureDefect::InvalidField));
+        assert_eq!(validate_iso_bmff(&mp4, Some("mov")), invalid("ftyp brand mp42", 8, StructureDefect::InvalidField));
+
+        // Corner cases
+        // Extensions of other formats are verified by the file type
+        assert_eq!(validate_iso_bmff(&mov, Some("xlsx")), Ok(()));
+        assert_eq!(validate_iso_bmff(&[], None), invalid("ftyp", 0, StructureDefect::Missing));
+        assert_eq!(validate_iso_bmff(&mp4[..4], None), invalid("box", 0, StructureDefect::Truncated));
+    }
+
+    #[test]
+    fn validate_iso_bmff_boxes() {
+        let file_type = ftyp(b"mp42");
+        let media_data = iso_box(b"mdat", &[0; 4]);
+
+        // Fail
+        assert_eq!(validate_iso_bmff(&[moov(), file_type.clone()].concat(), None), invalid("ftyp", 0, StructureDefect::Missing));
+        assert_eq!(validate_iso_bmff(&[file_type.clone(), media_data.clone()].concat(), None),
+                   invalid("moov", 32, StructureDefect::Missing));
+        assert_eq!(validate_iso_bmff(&[file_type.clone(), moov()].concat(), None), invalid("mdat", 40, StructureDefect::Missing));
+        assert_eq!(validate_iso_bmff(&[file_type.clone(), moov(), moov(), media_data.clone()].concat(), None),
+                   invalid("moov", 40, StructureDefect::Misplaced));
+        assert_eq!(validate_iso_bmff(&[file_type.clone(), file_type.clone(), moov(), media_data.clone()].concat(), None),
+                   invalid("ftyp", 20, StructureDefect::Misplaced));
+        assert_eq!(validate_iso_bmff(&iso_box(b"ftyp", b"mp4"), None), invalid("ftyp", 0, StructureDefect::InvalidField));
+        // Child exceeding its parent
+        let mut overflow = moov();
+        overflow[11] += 1;
+        assert_eq!(validate_iso_bmff(&[file_type.clone(), overflow, media_data.clone()].concat(), None),
+                   invalid("mvhd", 28, StructureDefect::Truncated));
+        // Size smaller than the header
+        let mut small = moov();
+        small[11] = 4;
+        assert_eq!(validate_iso_bmff(&[file_type.clone(), small, media_data.clone()].concat(), None),
+                   invalid("mvhd", 28, StructureDefect::InvalidField));
+
+        // Corner cases
+        // Large size, size extending to the end of the file and nesting at the limit
+        let large = [&1u32.to_be_bytes()[..], b"mdat", &20u64.to_be_bytes(), &[0; 4]].concat();
+        assert_eq!(validate_iso_bmff(&[file_type.clone(), moov(), large].concat(), None), Ok(()));
+        let to_end = [&0u32.to_be_bytes()[..], b"mdat", &[0; 10]].concat();
+        assert_eq!(validate_iso_bmff(&[file_type.clone(), moov(), to_end].concat(), None), Ok(()));
+        let mut nested = iso_box(b"mvhd", &[]);
+        for _ in 0..ISO_BMFF_MAX_DEPTH {
+            nested = iso_box(b"trak", &nested);
+        }
+        assert_eq!(validate_iso_bmff(&[file_type.clone(), nested.clone(), media_data.clone()].concat(), None),
+                   invalid("moov", 20 + nested.len() + 12, StructureDefect::Missing));
+        let too_deep = iso_box(b"moov", &nested);
+        assert_eq!(validate_iso_bmff(&[file_type, too_deep, media_data].concat(), None),
+                   invalid("trak", 20 + 8 * ISO_BMFF_MAX_DEPTH, StructureDefect::LimitExceeded));
+    }
+}
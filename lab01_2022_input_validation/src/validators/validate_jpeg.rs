@@ -0,0 +1,247 @@
+use crate::validators::binary_helper::{read_u16_be};
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+// Markers without a length and a payload (ITU T.81 table B.1)
+const SOI: u8 = 0xd8;
+const EOI: u8 = 0xd9;
+const SOS: u8 = 0xda;
+const TEM: u8 = 0x01;
+const RST_MARKERS: std::ops::RangeInclusive<u8> = 0xd0..=0xd7;
+// Fixed part of the SOF and SOS segments before the component specifications
+const SOF_HEADER_LENGTH: usize = 8;
+const SOS_HEADER_LENGTH: usize = 6;
+
+/// Give the name of a marker as written in the JPEG specification
+/// # Arguments
+/// * `marker` - The byte following the 0xFF prefix
+/// # Returns
+/// * `String` - The name of the marker
+fn marker_name(marker: u8) -> String {
+    match marker {
+        SOI => "SOI".to_string(),
+        EOI => "EOI".to_string(),
+        SOS => "SOS".to_string(),
+        TEM => "TEM".to_string(),
+        0xc4 => "DHT".to_string(),
+        0xcc => "DAC".to_string(),
+        0xdb => "DQT".to_string(),
+        0xdc => "DNL".to_string(),
+        0xdd => "DRI".to_string(),
+        0xfe => "COM".to_string(),
+        0xd0..=0xd7 => format!("RST{}", marker - 0xd0),
+        0xe0..=0xef => format!("APP{}", marker - 0xe0),
+        _ if is_sof(marker) => format!("SOF{}", marker - 0xc0),
+        _ => format!("marker 0x{:02x}", marker),
+    }
+}
+
+/// Check if the marker starts a frame, whatever its coding process
+/// # Arguments
+/// * `marker` - The byte following the 0xFF prefix
+/// # Returns
+/// * `bool` - True if the marker is a SOF marker, false otherwise
+fn is_sof(marker: u8) -> bool {
+    (0xc0..=0xcf).contains(&marker) && !matches!(marker, 0xc4 | 0xc8 | 0xcc)
+}
+
+/// Check if the marker is defined for the segments of a JPEG file, the reserved
+/// markers cannot be skipped
+/// # Arguments
+/// * `marker` - The byte following the 0xFF prefix
+/// # Returns
+/// * `bool` - True if the marker can be followed by a segment, false otherwise
+fn is_segment_marker(marker: u8) -> bool {
+    ((0xc0..=0xcf).contains(&marker) && marker != 0xc8) || (0xda..=0xfe).contains(&marker)
+}
+
+/// Check the fields of a SOF segment
+/// # Arguments
+/// * `data` - The payload of the segment, after its length
+/// # Returns
+/// * `bool` - True if the frame header is valid, false otherwise
+fn is_valid_frame_header(data: &[u8]) -> bool {
+    let width = read_u16_be(data, 3).unwrap_or(0);
+    let components = data.get(5).copied().unwrap_or(0) as usize;
+    data.len() + 2 == SOF_HEADER_LENGTH + 3 * components && components > 0 && width > 0
+}
+
+/// Check the fields of a SOS segment
+/// # Arguments
+/// * `data` - The payload of the segment, after its length
+/// # Returns
+/// * `bool` - True if the scan header is valid, false otherwise
+fn is_valid_scan_header(data: &[u8]) -> bool {
+    let components = data.first().copied().unwrap_or(0) as usize;
+    data.len() + 2 == SOS_HEADER_LENGTH + 2 * components && (1..=4).contains(&components)
+}
+
+/// Give the offset of the marker ending the entropy-coded data of a scan. The
+/// stuffed bytes (0xFF00) and the restart markers are part of the data
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `offset` - The offset of the first byte of the data
+/// # Returns
+/// * `Option<usize>` - The offset of the marker, None if the file ends before
+fn find_scan_end(buffer: &[u8], mut offset: usize) -> Option<usize> {
+    while offset + 1 < buffer.len() {
+        if buffer[offset] == 0xff {
+            let next = buffer[offset + 1];
+            if next != 0x00 && !RST_MARKERS.contains(&next) {
+                return Some(offset);
+            }
+            offset += 1;
+        }
+        offset += 1;
+    }
+    None
+}
+
+/// Validate the structure of a JPEG file by walking its segments. The file must start
+/// with SOI, every segment must fit in the file, a frame header (SOF) must precede the
+/// scans (SOS) and the EOI marker must end the file, so that appended data is rejected
+/// # Arguments
+/// * `buffer` - The content of the file
+/// # Return
+/// * `()` - If the structure is valid
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the first segment that is not valid
+pub fn validate_jpeg(buffer: &[u8]) -> Result<(), ValidationError> {
+    if !buffer.starts_with(&[0xff, SOI]) {
+        return Err(InvalidStructure::error("SOI", 0, StructureDefect::Missing));
+    }
+
+    let mut offset = 2;
+    let mut has_frame = false;
+    let mut has_scan = false;
+
+    while offset < buffer.len() {
+        if buffer[offset] != 0xff {
+            return Err(InvalidStructure::error("marker", offset, StructureDefect::InvalidField));
+        }
+        // Any number of 0xFF fill bytes can precede a marker
+        let Some(marker_offset) = (offset..buffer.len()).find(|&index| buffer[index] != 0xff).map(|index| index - 1) else {
+            return Err(InvalidStructure::error("marker", offset, StructureDefect::Truncated));
+        };
+        let marker = buffer[marker_offset + 1];
+        let name = marker_name(marker);
+        let error = |defect| InvalidStructure::error(&name, marker_offset, defect);
+
+        match marker {
+            EOI => {
+                if !has_scan {
+                    return Err(InvalidStructure::error("SOS", marker_offset, StructureDefect::Missing));
+                }
+                let end = marker_offset + 2;
+                if end != buffer.len() {
+                    return Err(InvalidStructure::error("EOI", end, StructureDefect::TrailingData));
+                }
+                return Ok(());
+            },
+            // Standalone markers are only allowed in the entropy-coded data or at the start
+            SOI | TEM | 0xd0..=0xd7 => return Err(error(StructureDefect::Misplaced)),
+            _ if !is_segment_marker(marker) => return Err(error(StructureDefect::InvalidField)),
+            _ => {},
+        }
+
+        let length = read_u16_be(buffer, marker_offset + 2).ok_or_else(|| error(StructureDefect::Truncated))? as usize;
+        if length < 2 {
+            return Err(error(StructureDefect::InvalidField));
+        }
+        let end = marker_offset + 2 + length;
+        let data = buffer.get(marker_offset + 4..end).ok_or_else(|| error(StructureDefect::Truncated))?;
+
+        if is_sof(marker) {
+            if has_frame {
+                return Err(error(StructureDefect::Misplaced));
+            }
+            if !is_valid_frame_header(data) {
+                return Err(error(StructureDefect::InvalidField));
+            }
+            has_frame = true;
+        }
+
+        offset = end;
+        if marker == SOS {
+            if !has_frame {
+                return Err(InvalidStructure::error("SOF", marker_offset, StructureDefect::Missing));
+            }
+            if !is_valid_scan_header(data) {
+                return Err(error(StructureDefect::InvalidField));
+            }
+            has_scan = true;
+            offset = find_scan_end(buffer, end).unwrap_or(buffer.len());
+        }
+    }
+
+    Err(InvalidStructure::error("EOI", buffer.len(), StructureDefect::Missing))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_jpeg::{validate_jpeg};
+    use crate::validators::file_helper::{read_from_path};
+    use crate::validators::validation_error::{StructureDefect};
+    use crate::validators::test_helper::{invalid, BASE_FILE_PATH};
+
+    static SOI: [u8; 2] = [0xff, 0xd8];
+    static EOI: [u8; 2] = [0xff, 0xd9];
+    // Frame of 1x1 pixel with one component
+    static SOF0: [u8; 13] = [0xff, 0xc0, 0x00, 0x0b, 0x08, 0x00, 0x01, 0x00, 0x01, 0x01, 0x01, 0x11, 0x00];
+    // Scan of one component followed by entropy-coded data with a stuffed byte and a restart marker
+    static SOS: [u8; 16] = [0xff, 0xda, 0x00, 0x08, 0x01, 0x01, 0x00, 0x00, 0x3f, 0x00,
+                            0x12, 0xff, 0x00, 0xff, 0xd0, 0x34];
+
+    #[test]
+    fn validate_jpeg_classical() {
+        let file = read_from_path(&format!("{}images/file_example_jpg.jpg", BASE_FILE_PATH)).unwrap();
+
+        // Pass
+        assert_eq!(validate_jpeg(&file), Ok(()));
+        assert_eq!(validate_jpeg(&[&SOI[..], &SOF0, &SOS, &EOI].concat()), Ok(()));
+        // Comment, application segment and fill bytes
+        let comment = [0xff, 0xfe, 0x00, 0x04, b'h', b'i'];
+        let app = [0xff, 0xff, 0xe1, 0x00, 0x02];
+        assert_eq!(validate_jpeg(&[&SOI[..], &comment, &app, &SOF0, &SOS, &SOS, &EOI].concat()), Ok(()));
+
+        // Fail
+        // Payload appended after EOI
+        let mut polyglot = file.clone();
+        polyglot.extend_from_slice(b"<?php system($_GET['cmd']); ?>");
+        assert_eq!(validate_jpeg(&polyglot), invalid("EOI", file.len(), StructureDefect::TrailingData));
+        // Truncated in the entropy-coded data or in a segment
+        assert_eq!(validate_jpeg(&file[..file.len() - 2]), invalid("EOI", file.len() - 2, StructureDefect::Missing));
+        assert_eq!(validate_jpeg(&file[..3000]), invalid("APP2", 20, StructureDefect::Truncated));
+
+        // Corner cases
+        assert_eq!(validate_jpeg(&[]), invalid("SOI", 0, StructureDefect::Missing));
+        assert_eq!(validate_jpeg(&SOI), invalid("EOI", 2, StructureDefect::Missing));
+        assert_eq!(validate_jpeg(&[&SOI[..], &[0xff, 0xff]].concat()), invalid("marker", 2, StructureDefect::Truncated));
+        assert_eq!(validate_jpeg(&[&SOI[..], &[0xff, 0xc0, 0x00]].concat()), invalid("SOF0", 2, StructureDefect::Truncated));
+    }
+
+    #[test]
+    fn validate_jpeg_segments() {
+        // Fail
+        assert_eq!(validate_jpeg(&[&SOI[..], &SOS, &EOI].concat()), invalid("SOF", 2, StructureDefect::Missing));
+        assert_eq!(validate_jpeg(&[&SOI[..], &SOF0, &EOI].concat()), invalid("SOS", 15, StructureDefect::Missing));
+        assert_eq!(validate_jpeg(&[&SOI[..], &SOF0, &SOF0, &SOS, &EOI].concat()), invalid("SOF0", 15, StructureDefect::Misplaced));
+        assert_eq!(validate_jpeg(&[&SOI[..], &SOI, &SOF0, &SOS, &EOI].concat()), invalid("SOI", 2, StructureDefect::Misplaced));
+        assert_eq!(validate_jpeg(&[&SOI[..], &[0xff, 0xd3], &EOI].concat()), invalid("RST3", 2, StructureDefect::Misplaced));
+        assert_eq!(validate_jpeg(&[&SOI[..], &[0xff, 0x02, 0x00, 0x02], &EOI].concat()),
+                   invalid("marker 0x02", 2, StructureDefect::InvalidField));
+        assert_eq!(validate_jpeg(&[&SOI[..], &[0xff, 0xfe, 0x00, 0x01], &EOI].concat()), invalid("COM", 2, StructureDefect::InvalidField));
+        assert_eq!(validate_jpeg(&[&SOI[..], &[0x00], &SOF0, &SOS, &EOI].concat()), invalid("marker", 2, StructureDefect::InvalidField));
+
+        // Corner cases
+        // Frame without width or with a wrong number of components
+        let mut no_width = SOF0;
+        no_width[7..9].copy_from_slice(&[0, 0]);
+        assert_eq!(validate_jpeg(&[&SOI[..], &no_width, &SOS, &EOI].concat()), invalid("SOF0", 2, StructureDefect::InvalidField));
+        let mut components = SOF0;
+        components[9] = 2;
+        assert_eq!(validate_jpeg(&[&SOI[..], &components, &SOS, &EOI].concat()), invalid("SOF0", 2, StructureDefect::InvalidField));
+        let mut scan = SOS;
+        scan[4] = 0;
+        assert_eq!(validate_jpeg(&[&SOI[..], &SOF0, &scan, &EOI].concat()), invalid("SOS", 15, StructureDefect::InvalidField));
+    }
+}
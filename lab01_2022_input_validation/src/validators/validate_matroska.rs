@@ -0,0 +1,468 @@
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+/// Maximum nesting of master elements, to bound the recursion on a crafted file
+pub const MATROSKA_MAX_DEPTH: usize = 16;
+/// Maximum number of elements read in a file, to bound the work done on a crafted file
+pub const MATROSKA_MAX_ELEMENTS: usize = 1 << 20;
+
+// Lengths of the element identifiers and sizes (EBMLMaxIDLength and EBMLMaxSizeLength)
+const MAX_ID_LENGTH: usize = 4;
+const MAX_SIZE_LENGTH: usize = 8;
+// Elements of the EBML header (RFC 8794 section 11.2)
+const EBML: u32 = 0x1a45dfa3;
+const EBML_READ_VERSION: u32 = 0x42f7;
+const EBML_MAX_ID_LENGTH: u32 = 0x42f2;
+const EBML_MAX_SIZE_LENGTH: u32 = 0x42f3;
+const DOC_TYPE: u32 = 0x4282;
+// Elements allowed anywhere
+const VOID: u32 = 0xec;
+const CRC_32: u32 = 0xbf;
+// Top level elements of a Matroska segment (RFC 9559 section 5.1)
+const SEGMENT: u32 = 0x18538067;
+const SEEK_HEAD: u32 = 0x114d9b74;
+const INFO: u32 = 0x1549a966;
+const TRACKS: u32 = 0x1654ae6b;
+const CUES: u32 = 0x1c53bb6b;
+const CLUSTER: u32 = 0x1f43b675;
+const CHAPTERS: u32 = 0x1043a770;
+const TAGS: u32 = 0x1254c367;
+const ATTACHMENTS: u32 = 0x1941a469;
+static SEGMENT_CHILDREN: [u32; 8] = [SEEK_HEAD, INFO, TRACKS, CUES, CLUSTER, CHAPTERS, TAGS, ATTACHMENTS];
+// Master elements whose children are read, the other elements being skipped
+static MASTER_ELEMENTS: [u32; 25] = [
+    EBML, SEGMENT, SEEK_HEAD, INFO, TRACKS, CUES, CLUSTER, CHAPTERS, TAGS, ATTACHMENTS,
+    0x4dbb, // Seek
+    0xae, // TrackEntry
+    0xe0, // Video
+    0xe1, // Audio
+    0x6d80, // ContentEncodings
+    0x6240, // ContentEncoding
+    0xbb, // CuePoint
+    0xb7, // CueTrackPositions
+    0xa0, // BlockGroup
+    0x45b9, // EditionEntry
+    0xb6, // ChapterAtom
+    0x7373, // Tag
+    0x63c0, // Targets
+    0x67c8, // SimpleTag
+    0x61a7, // AttachedFile
+];
+
+/// Element of an EBML file
+#[derive(Debug, Clone, Copy)]
+struct Element {
+    id: u32,
+    offset: usize,
+    // Offset of the data, following the identifier and the size
+    data: usize,
+    end: usize,
+    // The size is unknown, the element ends with its last child
+    unknown_size: bool,
+}
+
+/// Give the name of an element, as written in the Matroska specification
+/// # Arguments
+/// * `id` - The identifier of the element
+/// # Returns
+/// * `String` - The name of the element
+fn element_name(id: u32) -> String {
+    let name = match id {
+        EBML => "EBML header",
+        EBML_READ_VERSION => "EBMLReadVersion",
+        EBML_MAX_ID_LENGTH => "EBMLMaxIDLength",
+        EBML_MAX_SIZE_LENGTH => "EBMLMaxSizeLength",
+        DOC_TYPE => "DocType",
+        SEGMENT => "Segment",
+        SEEK_HEAD => "SeekHead",
+        INFO => "Info",
+        TRACKS => "Tracks",
+        CUES => "Cues",
+        CLUSTER => "Cluster",
+        CHAPTERS => "Chapters",
+        TAGS => "Tags",
+        ATTACHMENTS => "Attachments",
+        VOID => "Void",
+        _ => return format!("element 0x{:X}", id),
+    };
+    name.to_string()
+}
+
+/// Read a variable size integer, whose length is given by its leading zero bits
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `offset` - The offset of the integer
+/// * `max_length` - The maximum length of the integer
+/// # Returns
+/// * `Option<(u64, usize, bool)>` - The value without its length marker, the length and
+///   whether every bit of the value is set, None if the length exceeds the maximum
+fn read_vint(buffer: &[u8], offset: usize, max_length: usize) -> Option<(u64, usize, bool)> {
+    let first = *buffer.get(offset)?;
+    let length = first.leading_zeros() as usize + 1;
+    if length > max_length {
+        return None;
+    }
+    let bytes = buffer.get(offset..offset + length)?;
+    let value = bytes[1..].iter().fold(u64::from(first) & (0xff >> length), |value, &byte| value << 8 | u64::from(byte));
+    Some((value, length, value == (1 << (7 * length)) - 1))
+}
+
+/// Give the defect of a variable size integer that cannot be read
+/// # Arguments
+/// * `buffer` - The content of the file, up to the end of the parent element
+/// * `offset` - The offset of the integer
+/// * `max_length` - The maximum length of the integer
+/// # Returns
+/// * `StructureDefect` - `InvalidField` if the length exceeds the maximum, else `Truncated`
+fn vint_defect(buffer: &[u8], offset: usize, max_length: usize) -> StructureDefect {
+    match buffer.get(offset) {
+        Some(first) if first.leading_zeros() as usize >= max_length => StructureDefect::InvalidField,
+        _ => StructureDefect::Truncated,
+    }
+}
+
+/// Read an unsigned integer element
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `element` - The element to read
+/// # Returns
+/// * `Option<u64>` - The value, None if the element is longer than 8 bytes
+fn read_uint(buffer: &[u8], element: &Element) -> Option<u64> {
+    let data = &buffer[element.data..element.end];
+    (data.len() <= 8).then(|| data.iter().fold(0, |value, &byte| value << 8 | u64::from(byte)))
+}
+
+/// Reader of the elements of an EBML file, counting the elements read
+#[derive(Debug)]
+struct EbmlReader<'a> {
+    buffer: &'a [u8],
+    elements: usize,
+}
+
+impl EbmlReader<'_> {
+    /// Read the header of the element at the given offset
+    /// # Arguments
+    /// * `offset` - The offset of the element
+    /// * `parent_end` - The offset following the parent element, or the length of the file
+    /// # Return
+    /// * `Element` - The element
+    /// # Errors
+    /// * `ValidationError` - If the identifier or the size is invalid, if the element exceeds
+    ///   its parent or if too many elements have been read
+    fn read_element(&mut self, offset: usize, parent_end: usize) -> Result<Element, ValidationError> {
+        self.elements += 1;
+        if self.elements > MATROSKA_MAX_ELEMENTS {
+            return Err(InvalidStructure::error("element", offset, StructureDefect::LimitExceeded));
+        }
+        let window = &self.buffer[..parent_end];
+        let (_, id_length, id_all_ones) = read_vint(window, offset, MAX_ID_LENGTH)
+            .ok_or_else(|| InvalidStructure::error("element", offset, vint_defect(window, offset, MAX_ID_LENGTH)))?;
+        // The identifier keeps its length marker
+        let id = window[offset..offset + id_length].iter().fold(0, |id, &byte| id << 8 | u32::from(byte));
+        let error = |defect| InvalidStructure::error(&element_name(id), offset, defect);
+        if id_all_ones {
+            return Err(error(StructureDefect::InvalidField));
+        }
+
+        let (size, size_length, unknown_size) = read_vint(window, offset + id_length, MAX_SIZE_LENGTH)
+            .ok_or_else(|| error(vint_defect(window, offset + id_length, MAX_SIZE_LENGTH)))?;
+        let data = offset + id_length + size_length;
+        if unknown_size {
+            // Only the segment and the clusters can be written before their size is known
+            if id != SEGMENT && id != CLUSTER {
+                return Err(error(StructureDefect::InvalidField));
+            }
+            return Ok(Element { id, offset, data, end: parent_end, unknown_size });
+        }
+        let end = usize::try_from(size).ok().and_then(|size| data.checked_add(size)).filter(|&end| end <= parent_end)
+            .ok_or_else(|| error(StructureDefect::Truncated))?;
+        Ok(Element { id, offset, data, end, unknown_size })
+    }
+
+    /// Read the children of a master element, recursively
+    /// # Arguments
+    /// * `parent` - The master element
+    /// * `depth` - The nesting level of the parent, 1 for a top level element
+    /// # Return
+    /// * `(Vec<Element>, usize)` - The children of the element and the offset following
+    ///   the element, which is the end of its last child if its size is unknown
+    /// # Errors
+    /// * `ValidationError` - If a child is not valid or the nesting is too deep
+    fn read_children(&mut self, parent: &Element, depth: usize) -> Result<(Vec<Element>, usize), ValidationError> {
+        if depth > MATROSKA_MAX_DEPTH {
+            return Err(InvalidStructure::error(&element_name(parent.id), parent.offset, StructureDefect::LimitExceeded));
+        }
+        let mut children = Vec::new();
+        let mut offset = parent.data;
+        while offset < parent.end {
+            let child = self.read_element(offset, parent.end)?;
+            // A cluster of unknown size ends with the next element of the segment
+            if parent.unknown_size && parent.id == CLUSTER && SEGMENT_CHILDREN.contains(&child.id) {
+                break;
+            }
+            offset = match MASTER_ELEMENTS.contains(&child.id) {
+                true => self.read_children(&child, depth + 1)?.1,
+                false => child.end,
+            };
+            children.push(child);
+        }
+        Ok((children, offset))
+    }
+}
+
+/// Check if the document type is expected for the given extension
+/// # Arguments
+/// * `doc_type` - The document type of the EBML header
+/// * `extension` - The extension of the file, in lowercase
+/// # Returns
+/// * `Option<bool>` - True if the document type matches the extension, None if the
+///   extension is not a Matroska extension
+fn doc_type_matches_extension(doc_type: &str, extension: &str) -> Option<bool> {
+    match extension {
+        "webm" => Some(doc_type == "webm"),
+        "mkv" | "mka" | "mks" | "mk3d" => Some(doc_type == "matroska"),
+        _ => None,
+    }
+}
+
+/// Check the fields of the EBML header and its document type
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `header` - The EBML header
+/// * `children` - The elements of the header
+/// * `extension` - The extension of the file, None if it is not verified
+/// # Return
+/// * `()` - If the header is valid and the document type matches the extension
+/// # Errors
+/// * `ValidationError` - If a field is not supported or the document type is missing or
+///   does not match the extension
+fn check_ebml_header(buffer: &[u8], header: &Element, children: &[Element], extension: Option<&str>) -> Result<(), ValidationError> {
+    let mut doc_type = None;
+    for child in children {
+        let value = read_uint(buffer, child);
+        let supported = match child.id {
+            EBML_READ_VERSION => value == Some(1),
+            EBML_MAX_ID_LENGTH => value == Some(MAX_ID_LENGTH as u64),
+            EBML_MAX_SIZE_LENGTH => value.is_some_and(|length| (1..=MAX_SIZE_LENGTH as u64).contains(&length)),
+            DOC_TYPE => {
+                // Strings can be padded with null bytes
+                let value = String::from_utf8_lossy(&buffer[child.data..child.end]).trim_end_matches('\0').to_string();
+                doc_type = Some((value, child.offset));
+                true
+            },
+            _ => true,
+        };
+        if !supported {
+            return Err(InvalidStructure::error(&element_name(child.id), child.offset, StructureDefect::Unsupported));
+        }
+    }
+
+    let (doc_type, offset) = doc_type.ok_or_else(|| InvalidStructure::error("DocType", header.end, StructureDefect::Missing))?;
+    if doc_type != "webm" && doc_type != "matroska" {
+        return Err(InvalidStructure::error(&format!("DocType {}", doc_type), offset, StructureDefect::Unsupported));
+    }
+    if extension.and_then(|extension| doc_type_matches_extension(&doc_type, extension)) == Some(false) {
+        return Err(InvalidStructure::error(&format!("DocType {}", doc_type), offset, StructureDefect::InvalidField));
+    }
+    Ok(())
+}
+
+/// Check that the segment contains exactly one Info and one Tracks element
+/// # Arguments
+/// * `children` - The elements of the segment
+/// * `end` - The offset following the segment
+/// # Return
+/// * `()` - If the segment is valid
+/// # Errors
+/// * `ValidationError` - If the Info or Tracks element is missing or repeated
+fn check_segment(children: &[Element], end: usize) -> Result<(), ValidationError> {
+    for id in [INFO, TRACKS] {
+        let mut elements = children.iter().filter(|child| child.id == id);
+        if elements.next().is_none() {
+            return Err(InvalidStructure::error(&element_name(id), end, StructureDefect::Missing));
+        }
+        if let Some(repeated) = elements.next() {
+            return Err(InvalidStructure::error(&element_name(id), repeated.offset, StructureDefect::Misplaced));
+        }
+    }
+    Ok(())
+}
+
+/// Validate the structure of a Matroska or WebM file. The file must start with an EBML
+/// header whose document type matches the extension, followed by a segment containing
+/// its Info and Tracks elements. Every element must fit in its parent, the nesting is
+/// limited to `MATROSKA_MAX_DEPTH` levels, the number of elements to
+/// `MATROSKA_MAX_ELEMENTS` and only Void elements can follow the segment
+/// # Arguments
+/// * `buffer` - The content of the file
+/// * `extension` - The extension of the file to compare with the document type, None
+///   if it is not verified
+/// # Return
+/// * `()` - If the structure is valid
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the first element that is not valid
+pub fn validate_matroska(buffer: &[u8], extension: Option<&str>) -> Result<(), ValidationError> {
+    let mut reader = EbmlReader { buffer, elements: 0 };
+
+    let header = reader.read_element(0, buffer.len())?;
+    if header.id != EBML {
+        return Err(InvalidStructure::error("EBML header", 0, StructureDefect::Missing));
+    }
+    let (children, mut offset) = reader.read_children(&header, 1)?;
+    check_ebml_header(buffer, &header, &children, extension)?;
+
+    let mut has_segment = false;
+    while offset < buffer.len() {
+        let element = reader.read_element(offset, buffer.len());
+        match element {
+            Ok(element) if element.id == VOID || element.id == CRC_32 => offset = element.end,
+            Ok(element) if element.id == SEGMENT && !has_segment => {
+                let (children, end) = reader.read_children(&element, 1)?;
+                check_segment(&children, end)?;
+                has_segment = true;
+                offset = end;
+            },
+            // Once the segment is read, the following bytes have been appended, unless the limit is reached
+            _ if has_segment && reader.elements <= MATROSKA_MAX_ELEMENTS => return Err(InvalidStructure::error("Segment", offset, StructureDefect::TrailingData)),
+            Ok(element) => return Err(InvalidStructure::error(&element_name(element.id), offset, StructureDefect::Misplaced)),
+            Err(error) => return Err(error),
+        }
+    }
+    if !has_segment {
+        return Err(InvalidStructure::error("Segment", buffer.len(), StructureDefect::Missing));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_matroska::{validate_matroska, MATROSKA_MAX_DEPTH, MATROSKA_MAX_ELEMENTS};
+    use crate::validators::file_helper::{read_from_path};
+    use crate::validators::validation_error::{StructureDefect};
+    use crate::validators::test_helper::{invalid, BASE_FILE_PATH};
+
+    // Offsets of the elements of the fixture
+    const DOC_TYPE_OFFSET: usize = 28;
+    const SEGMENT_OFFSET: usize = 43;
+    // Size of unknown length
+    static UNKNOWN_SIZE: [u8; 1] = [0xff];
+
+    /// Build an element from its identifier and data, with a size of 1 or 8 bytes
+    fn element(id: &[u8], data: &[u8]) -> Vec<u8> {
+        let size = if data.len() < 0x7f {
+            vec![0x80 | data.len() as u8]
+        } else {
+            [&[0x01][..], &(data.len() as u64).to_be_bytes()[1..]].concat()
+        };
+        [id, &size, data].concat()
+    }
+
+    /// Build an EBML header with the given document type
+    fn header(doc_type: &str) -> Vec<u8> {
+        element(&[0x1a, 0x45, 0xdf, 0xa3], &[element(&[0x42, 0x86], &[1]), element(&[0x42, 0x82], doc_type.as_bytes())].concat())
+    }
+
+    /// Build a segment containing the given elements after an Info and a Tracks element
+    fn segment(elements: &[Vec<u8>]) -> Vec<u8> {
+        let info = element(&[0x15, 0x49, 0xa9, 0x66], &element(&[0x2a, 0xd7, 0xb1], &[0x0f, 0x42, 0x40]));
+        let tracks = element(&[0x16, 0x54, 0xae, 0x6b], &element(&[0xae], &element(&[0xd7], &[1])));
+        element(&[0x18, 0x53, 0x80, 0x67], &[info, tracks, elements.concat()].concat())
+    }
+
+    /// Build a cluster containing a simple block
+    fn cluster() -> Vec<u8> {
+        element(&[0x1f, 0x43, 0xb6, 0x75], &[element(&[0xe7], &[0]), element(&[0xa3], &[0x81, 0, 0, 0x80])].concat())
+    }
+
+    #[test]
+    fn validate_matroska_classical() {
+        let file = read_from_path(&format!("{}videos/file_example_webm.webm", BASE_FILE_PATH)).unwrap();
+
+        // Pass
+        assert_eq!(validate_matroska(&file, Some("webm")), Ok(()));
+        assert_eq!(validate_matroska(&file, None), Ok(()));
+        assert_eq!(validate_matroska(&[header("matroska"), segment(&[cluster()])].concat(), Some("mkv")), Ok(()));
+        // Void element following the segment
+        assert_eq!(validate_matroska(&[header("webm"), segment(&[]), element(&[0xec], &[0; 4])].concat(), Some("webm")), Ok(()));
+
+        // Fail
+        let mut junk = file.clone();
+        junk.extend_from_slice(b"<?php echo 'test'; ?>");
+        assert_eq!(validate_matroska(&junk, Some("webm")), invalid("Segment", file.len(), StructureDefect::TrailingData));
+        assert_eq!(validate_matroska(&file[..file.len() - 1], Some("webm")), invalid("Segment", SEGMENT_OFFSET, StructureDefect::Truncated));
+        // Document type not matching the extension
+        assert_eq!(validate_matroska(&file, Some("mkv")), invalid("DocType webm", DOC_TYPE_OFFSET, StructureDefect::InvalidField));
+        let file = [header("matroska"), segment(&[])].concat();
+        assert_eq!(validate_matroska(&file, Some("webm")), invalid("DocType matroska", 9, StructureDefect::InvalidField));
+
+        // Corner cases
+        assert_eq!(validate_matroska(&file, Some("xlsx")), Ok(()));
+        assert_eq!(validate_matroska(&[], None), invalid("element", 0, StructureDefect::Truncated));
+        assert_eq!(validate_matroska(&header("webm"), None), invalid("Segment", header("webm").len(), StructureDefect::Missing));
+        assert_eq!(validate_matroska(&segment(&[]), None), invalid("EBML header", 0, StructureDefect::Missing));
+    }
+
+    #[test]
+    fn validate_matroska_header() {
+        // Fail
+        let file = [header("mp4"), segment(&[])].concat();
+        assert_eq!(validate_matroska(&file, None), invalid("DocType mp4", 9, StructureDefect::Unsupported));
+        let no_doc_type = element(&[0x1a, 0x45, 0xdf, 0xa3], &element(&[0x42, 0x86], &[1]));
+        assert_eq!(validate_matroska(&[no_doc_type, segment(&[])].concat(), None), invalid("DocType", 9, StructureDefect::Missing));
+        let read_version = element(&[0x1a, 0x45, 0xdf, 0xa3], &[element(&[0x42, 0xf7], &[2]), element(&[0x42, 0x82], b"webm")].concat());
+        assert_eq!(validate_matroska(&[read_version, segment(&[])].concat(), None), invalid("EBMLReadVersion", 5, StructureDefect::Unsupported));
+
+        // Corner cases
+        // Document type padded with null bytes
+        assert_eq!(validate_matroska(&[header("webm\0\0"), segment(&[])].concat(), Some("webm")), Ok(()));
+    }
+
+    #[test]
+    fn validate_matroska_segment() {
+        let header = header("webm");
+        let segment_offset = header.len();
+
+        // Pass
+        // Segment and clusters of unknown size
+        let clusters = [&[0x1f, 0x43, 0xb6, 0x75][..], &UNKNOWN_SIZE, &element(&[0xe7], &[0]), &cluster()].concat();
+        let content = &segment(&[clusters])[5..];
+        let file = [&header[..], &[0x18, 0x53, 0x80, 0x67], &UNKNOWN_SIZE, content].concat();
+        assert_eq!(validate_matroska(&file, None), Ok(()));
+
+        // Fail
+        let info_only = element(&[0x18, 0x53, 0x80, 0x67], &element(&[0x15, 0x49, 0xa9, 0x66], &[]));
+        assert_eq!(validate_matroska(&[header.clone(), info_only.clone()].concat(), None),
+                   invalid("Tracks", segment_offset + info_only.len(), StructureDefect::Missing));
+        let info = element(&[0x15, 0x49, 0xa9, 0x66], &[]);
+        let file = [header.clone(), segment(&[info])].concat();
+        assert_eq!(validate_matroska(&file, None), invalid("Info", file.len() - 5, StructureDefect::Misplaced));
+        assert_eq!(validate_matroska(&[header.clone(), segment(&[]), segment(&[])].concat(), None),
+                   invalid("Segment", segment_offset + segment(&[]).len(), StructureDefect::TrailingData));
+        assert_eq!(validate_matroska(&[header.clone(), cluster(), segment(&[])].concat(), None),
+                   invalid("Cluster", segment_offset, StructureDefect::Misplaced));
+        // Element exceeding its parent and element of unknown size that cannot have one
+        let mut overflow = segment(&[cluster()]);
+        let cluster_offset = overflow.len() - cluster().len();
+        overflow[cluster_offset + 4] += 1;
+        assert_eq!(validate_matroska(&[header.clone(), overflow].concat(), None),
+                   invalid("Cluster", segment_offset + cluster_offset, StructureDefect::Truncated));
+        let unknown = [&[0x16, 0x54, 0xae, 0x6b][..], &UNKNOWN_SIZE].concat();
+        assert_eq!(validate_matroska(&[header.clone(), segment(&[unknown])].concat(), None),
+                   invalid("Tracks", segment_offset + segment(&[]).len(), StructureDefect::InvalidField));
+
+        // Corner cases
+        // Nesting and number of elements at the limits
+        let mut nested = element(&[0x67, 0xc8], &[]);
+        for _ in 0..MATROSKA_MAX_DEPTH - 4 {
+            nested = element(&[0x67, 0xc8], &nested);
+        }
+        let tags = |content: &[u8]| element(&[0x12, 0x54, 0xc3, 0x67], &element(&[0x73, 0x73], content));
+        assert_eq!(validate_matroska(&[header.clone(), segment(&[tags(&nested)])].concat(), None), Ok(()));
+        let too_deep = element(&[0x67, 0xc8], &nested);
+        let file = [header.clone(), segment(&[tags(&too_deep)])].concat();
+        assert_eq!(validate_matroska(&file, None),
+                   invalid("element 0x67C8", segment_offset + 35 + 3 * (MATROSKA_MAX_DEPTH - 3), StructureDefect::LimitExceeded));
+        let voids = [0xec, 0x80].repeat(MATROSKA_MAX_ELEMENTS);
+        let file = [header.clone(), segment(&[]), voids].concat();
+        assert_eq!(validate_matroska(&file, None), invalid("element", file.len() - 2 * 9, StructureDefect::LimitExceeded));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::validators::binary_helper::{read_u32_be, four_cc_name};
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+// Eight bytes starting every PNG file
+static PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+// Lengths and dimensions are limited to 2^31 - 1 (PNG specification section 7.1)
+const PNG_MAX_LENGTH: u32 = 0x7fff_ffff;
+// Length, type and CRC fields surrounding the data of a chunk
+const CHUNK_OVERHEAD: usize = 12;
+const IHDR_LENGTH: u32 = 13;
+// A palette contains at most 256 entries of 3 bytes
+const PLTE_MAX_LENGTH: u32 = 768;
+
+/// Table of the CRC-32 used by PNG (ISO 3309 polynomial, reflected)
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut crc = n as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+            bit += 1;
+        }
+        table[n] = crc;
+        n += 1;
+    }
+    table
+};
+
+/// Compute the CRC-32 of the given bytes as stored in a PNG chunk
+/// # Arguments
+/// * `bytes` - The chunk type followed by the chunk data
+/// # Returns
+/// * `u32` - The CRC of the bytes
+pub(crate) fn png_crc(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| CRC_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// Check if the bit depth is allowed for the colour type (PNG specification table 11.1)
+/// # Arguments
+/// * `colour_type` - The colour type of the image
+/// * `bit_depth` - The number of bits per sample or palette index
+/// # Returns
+/// * `bool` - True if the combination is allowed, false otherwise
+fn is_valid_bit_depth(colour_type: u8, bit_depth: u8) -> bool {
+    match colour_type {
+        0 => matches!(bit_depth, 1 | 2 | 4 | 8 | 16),
+        3 => matches!(bit_depth, 1 | 2 | 4 | 8),
+        2 | 4 | 6 => matches!(bit_depth, 8 | 16),
+        _ => false,
+    }
+}
+
+/// Check the fields of the IHDR chunk
+/// # Arguments
+/// * `data` - The data of the chunk
+/// * `offset` - The offset of the chunk
+/// # Return
+/// * `u8` - The colour type of the image
+/// # Errors
+/// * `ValidationError` - If a field is not valid
+fn check_ihdr(data: &[u8], offset: usize) -> Result<u8, ValidationError> {
+    let invalid = || InvalidStructure::error("IHDR", offset, StructureDefect::InvalidField);
+
+    if data.len() != IHDR_LENGTH as usize {
+        return Err(invalid());
+    }
+    let width = read_u32_be(data, 0).ok_or_else(invalid)?;
+    let height = read_u32_be(data, 4).ok_or_else(invalid)?;
+    let (bit_depth, colour_type, compression, filter, interlace) = (data[8], data[9], data[10], data[11], data[12]);
+
+    if !(1..=PNG_MAX_LENGTH).contains(&width) || !(1..=PNG_MAX_LENGTH).contains(&height)
+        || !is_valid_bit_depth(colour_type, bit_depth)
+        || compression != 0 || filter != 0 || interlace > 1 {
+        return Err(invalid());
+    }
+    Ok(colour_type)
+}
+
+/// Validate the structure of a PNG file by walking its chunks. The IHDR chunk must be
+/// first and valid, the CRC of every chunk must match, the palette and image data
+/// chunks must be well placed and the IEND chunk must end the file
+/// # Arguments
+/// * `buffer` - The content of the file
+/// # Return
+/// * `()` - If the structure is valid
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the first chunk that is not valid
+pub fn validate_png(buffer: &[u8]) -> Result<(), ValidationError> {
+    if !buffer.starts_with(&PNG_SIGNATURE) {
+        return Err(InvalidStructure::error("signature", 0, StructureDefect::InvalidField));
+    }
+
+    let mut offset = PNG_SIGNATURE.len();
+    let mut colour_type = None;
+    let mut has_palette = false;
+    // The IDAT chunks must be consecutive
+    let mut has_idat = false;
+    let mut idat_ended = false;
+
+    while offset < buffer.len() {
+        // The length and the type are needed to name the chunk
+        let Some(chunk_type) = buffer.get(offset + 4..offset + 8) else {
+            return Err(InvalidStructure::error("chunk", offset, StructureDefect::Truncated));
+        };
+        let name = four_cc_name(chunk_type);
+        let error = |defect| InvalidStructure::error(&name, offset, defect);
+
+        let length = read_u32_be(buffer, offset).ok_or_else(|| error(StructureDefect::Truncated))?;
+        if length > PNG_MAX_LENGTH || !chunk_type.iter().all(u8::is_ascii_alphabetic) {
+            return Err(error(StructureDefect::InvalidField));
+        }
+        let length = length as usize;
+        if buffer.len() - offset < CHUNK_OVERHEAD + length {
+            return Err(error(StructureDefect::Truncated));
+        }
+        let data = &buffer[offset + 8..offset + 8 + length];
+        let crc = read_u32_be(buffer, offset + 8 + length).ok_or_else(|| error(StructureDefect::Truncated))?;
+        if png_crc(&buffer[offset + 4..offset + 8 + length]) != crc {
+            return Err(error(StructureDefect::InvalidChecksum));
+        }
+
+        if colour_type.is_none() && chunk_type != b"IHDR" {
+            return Err(InvalidStructure::error("IHDR", offset, StructureDefect::Missing));
+        }
+        if has_idat && chunk_type != b"IDAT" {
+            idat_ended = true;
+        }
+
+        match chunk_type {
+            b"IHDR" => {
+                if colour_type.is_some() {
+                    return Err(error(StructureDefect::Misplaced));
+                }
+                colour_type = Some(check_ihdr(data, offset)?);
+            },
+            b"PLTE" => {
+                if has_palette || has_idat || matches!(colour_type, Some(0 | 4)) {
+                    return Err(error(StructureDefect::Misplaced));
+                }
+                if length == 0 || length % 3 != 0 || length > PLTE_MAX_LENGTH as usize {
+                    return Err(error(StructureDefect::InvalidField));
+                }
+                has_palette = true;
+            },
+            b"IDAT" => {
+                if idat_ended {
+                    return Err(error(StructureDefect::Misplaced));
+                }
+                if colour_type == Some(3) && !has_palette {
+                    return Err(InvalidStructure::error("PLTE", offset, StructureDefect::Missing));
+                }
+                has_idat = true;
+            },
+            b"IEND" => {
+                if length != 0 {
+                    return Err(error(StructureDefect::InvalidField));
+                }
+                if !has_idat {
+                    return Err(InvalidStructure::error("IDAT", offset, StructureDefect::Missing));
+                }
+                let end = offset + CHUNK_OVERHEAD;
+                if end != buffer.len() {
+                    return Err(InvalidStructure::error("IEND", end, StructureDefect::TrailingData));
+                }
+                return Ok(());
+            },
+            // The first letter of a critical chunk is uppercase, such chunks cannot be ignored
+            _ if chunk_type[0].is_ascii_uppercase() => return Err(error(StructureDefect::Unsupported)),
+            _ => {},
+        }
+        offset += CHUNK_OVERHEAD + length;
+    }
+
+    let missing = if colour_type.is_none() { "IHDR" } else { "IEND" };
+    Err(InvalidStructure::error(missing, buffer.len(), StructureDefect::Missing))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_png::{validate_png, png_crc};
+    use crate::validators::file_helper::{read_from_path};
+    use crate::validators::validation_error::{StructureDefect};
+    use crate::validators::test_helper::{invalid, BASE_FILE_PATH};
+
+    static SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+    /// Build a chunk with a valid CRC
+    fn chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(chunk_type);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&png_crc(&chunk[4..]).to_be_bytes());
+        chunk
+    }
+
+    /// Build the data of an IHDR chunk
+    fn ihdr(width: u32, height: u32, bit_depth: u8, colour_type: u8) -> Vec<u8> {
+        let mut data = width.to_be_bytes().to_vec();
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&[bit_depth, colour_type, 0, 0, 0]);
+        data
+    }
+
+    #[test]
+    fn png_crc_classical() {
+        // Pass
+        assert_eq!(png_crc(b"IEND"), 0xae42_6082);
+        assert_eq!(png_crc(b"123456789"), 0xcbf4_3926);
+
+        // Corner cases
+        assert_eq!(png_crc(b""), 0);
+    }
+
+    #[test]
+    fn validate_png_classical() {
+        let file = read_from_path(&format!("{}images/file_example_png.png", BASE_FILE_PATH)).unwrap();
+
+        // Pass
+        assert_eq!(validate_png(&file), Ok(()));
+        let minimal = [SIGNATURE.to_vec(), chunk(b"IHDR", &ihdr(1, 1, 8, 0)), chunk(b"IDAT", &[0]), chunk(b"IEND", &[])].concat();
+        assert_eq!(validate_png(&minimal), Ok(()));
+
+        // Fail
+        // Polyglot: data appended after IEND
+        let mut polyglot = file.clone();
+        polyglot.extend_from_slice(b"<?php echo 'test'; ?>");
+        assert_eq!(validate_png(&polyglot), invalid("IEND", file.len(), StructureDefect::TrailingData));
+        // Truncated inside the last chunk
+        assert_eq!(validate_png(&file[..file.len() - 4]), invalid("IEND", file.len() - 12, StructureDefect::Truncated));
+        // Corrupted CRC of the IHDR chunk
+        let mut corrupted = file.clone();
+        corrupted[29] ^= 0xff;
+        assert_eq!(validate_png(&corrupted), invalid("IHDR", 8, StructureDefect::InvalidChecksum));
+
+        // Corner cases
+        assert_eq!(validate_png(&[]), invalid("signature", 0, StructureDefect::InvalidField));
+        assert_eq!(validate_png(&SIGNATURE), invalid("IHDR", 8, StructureDefect::Missing));
+        assert_eq!(validate_png(&file[..file.len() - 12]), invalid("IEND", file.len() - 12, StructureDefect::Missing));
+        assert_eq!(validate_png(&file[..file.len() - 6]), invalid("chunk", file.len() - 12, StructureDefect::Truncated));
+        assert_eq!(validate_png(&minimal[..minimal.len() - 5]), invalid("chunk", minimal.len() - 12, StructureDefect::Truncated));
+    }
+
+    #[test]
+    fn validate_png_ihdr() {
+        let idat = chunk(b"IDAT", &[0]);
+        let iend = chunk(b"IEND", &[]);
+
+        // Pass
+        for (bit_depth, colour_type) in [(1, 0), (16, 0), (16, 2), (8, 4), (16, 6)] {
+            let file = [SIGNATURE.to_vec(), chunk(b"IHDR", &ihdr(2, 3, bit_depth, colour_type)), idat.clone(), iend.clone()].concat();
+            assert_eq!(validate_png(&file), Ok(()));
+        }
+
+        // Fail
+        for data in [ihdr(0, 1, 8, 0), ihdr(1, 0x8000_0000, 8, 0), ihdr(1, 1, 16, 3), ihdr(1, 1, 4, 2),
+                     ihdr(1, 1, 8, 5), ihdr(1, 1, 8, 0)[..12].to_vec()] {
+            let file = [SIGNATURE.to_vec(), chunk(b"IHDR", &data), idat.clone(), iend.clone()].concat();
+            assert_eq!(validate_png(&file), invalid("IHDR", 8, StructureDefect::InvalidField));
+        }
+        let mut interlaced = ihdr(1, 1, 8, 0);
+        interlaced[12] = 2;
+        let file = [SIGNATURE.to_vec(), chunk(b"IHDR", &interlaced), idat.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), invalid("IHDR", 8, StructureDefect::InvalidField));
+
+        // Corner cases
+        let file = [SIGNATURE.to_vec(), chunk(b"tEXt", b"a\0b"), chunk(b"IHDR", &ihdr(1, 1, 8, 0)), idat.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), invalid("IHDR", 8, StructureDefect::Missing));
+        let header = chunk(b"IHDR", &ihdr(1, 1, 8, 0));
+        let file = [SIGNATURE.to_vec(), header.clone(), header.clone(), idat, iend].concat();
+        assert_eq!(validate_png(&file), invalid("IHDR", 8 + header.len(), StructureDefect::Misplaced));
+    }
+
+    #[test]
+    fn validate_png_chunks() {
+        let header = chunk(b"IHDR", &ihdr(1, 1, 8, 3));
+        let palette = chunk(b"PLTE", &[0, 0, 0]);
+        let idat = chunk(b"IDAT", &[0]);
+        let iend = chunk(b"IEND", &[]);
+        let text = chunk(b"tEXt", b"a\0b");
+        let first = 8 + header.len();
+
+        // Pass
+        let file = [SIGNATURE.to_vec(), header.clone(), palette.clone(), idat.clone(), idat.clone(), text.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), Ok(()));
+
+        // Fail
+        let file = [SIGNATURE.to_vec(), header.clone(), idat.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), invalid("PLTE", first, StructureDefect::Missing));
+        let file = [SIGNATURE.to_vec(), header.clone(), palette.clone(), idat.clone(), text.clone(), idat.clone(), iend.clone()].concat();
+        let second_idat = first + palette.len() + idat.len() + text.len();
+        assert_eq!(validate_png(&file), invalid("IDAT", second_idat, StructureDefect::Misplaced));
+        let file = [SIGNATURE.to_vec(), header.clone(), palette.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), invalid("IDAT", first + palette.len(), StructureDefect::Missing));
+        let file = [SIGNATURE.to_vec(), header.clone(), chunk(b"PLTE", &[0, 0]), idat.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), invalid("PLTE", first, StructureDefect::InvalidField));
+        let file = [SIGNATURE.to_vec(), chunk(b"IHDR", &ihdr(1, 1, 8, 0)), palette.clone(), idat.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), invalid("PLTE", first, StructureDefect::Misplaced));
+        let file = [SIGNATURE.to_vec(), header.clone(), palette.clone(), idat.clone(), chunk(b"IEND", &[0])].concat();
+        assert_eq!(validate_png(&file), invalid("IEND", first + palette.len() + idat.len(), StructureDefect::InvalidField));
+
+        // Corner cases
+        // Unknown critical chunk, unknown ancillary chunks are ignored
+        let file = [SIGNATURE.to_vec(), header.clone(), chunk(b"ABCD", &[]), palette.clone(), idat.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), invalid("ABCD", first, StructureDefect::Unsupported));
+        let file = [SIGNATURE.to_vec(), header.clone(), chunk(b"ab\0d", &[]), palette.clone(), idat.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), invalid("ab\\x00d", first, StructureDefect::InvalidField));
+        let mut huge = chunk(b"tEXt", &[]);
+        huge[..4].copy_from_slice(&0x8000_0000u32.to_be_bytes());
+        let file = [SIGNATURE.to_vec(), header.clone(), huge, palette.clone(), idat.clone(), iend.clone()].concat();
+        assert_eq!(validate_png(&file), invalid("tEXt", first, StructureDefect::InvalidField));
+        let mut too_long = chunk(b"tEXt", &[]);
+        too_long[..4].copy_from_slice(&1000u32.to_be_bytes());
+        let file = [SIGNATURE.to_vec(), header, too_long, iend].concat();
+        assert_eq!(validate_png(&file), invalid("tEXt", first, StructureDefect::Truncated));
+    }
+}
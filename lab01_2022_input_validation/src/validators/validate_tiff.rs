@@ -0,0 +1,354 @@
+use std::collections::HashSet;
+
+use crate::validators::binary_helper::{read_u16_be, read_u16_le, read_u32_be, read_u32_le};
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+/// Maximum number of IFDs walked in a file, to bound the work done on a crafted file
+pub const TIFF_MAX_IFDS: usize = 256;
+/// Maximum number of tags of an IFD
+pub const TIFF_MAX_TAGS: u16 = 1024;
+
+const TIFF_MAGIC: u16 = 42;
+const TIFF_HEADER_LENGTH: usize = 8;
+// Count, entries of 12 bytes and offset of the next IFD
+const IFD_ENTRY_LENGTH: usize = 12;
+// Values of at most 4 bytes are stored in the entry instead of at an offset
+const INLINE_VALUE_LENGTH: usize = 4;
+// Tags giving the location of the image data
+const TAG_IMAGE_WIDTH: u16 = 256;
+const TAG_IMAGE_LENGTH: u16 = 257;
+const TAG_STRIP_OFFSETS: u16 = 273;
+const TAG_STRIP_BYTE_COUNTS: u16 = 279;
+const TAG_TILE_OFFSETS: u16 = 324;
+const TAG_TILE_BYTE_COUNTS: u16 = 325;
+// Field types storing integers usable as offsets and lengths
+const TYPE_SHORT: u16 = 3;
+const TYPE_LONG: u16 = 4;
+
+/// Reader of the integers of a TIFF file in its byte order
+#[derive(Debug, Clone, Copy)]
+struct TiffReader<'a> {
+    buffer: &'a [u8],
+    little_endian: bool,
+}
+
+impl TiffReader<'_> {
+    /// Read a 16 bits integer
+    /// # Arguments
+    /// * `offset` - The offset of the integer
+    /// # Returns
+    /// * `Option<u16>` - The integer, None if it exceeds the file
+    fn u16(&self, offset: usize) -> Option<u16> {
+        if self.little_endian { read_u16_le(self.buffer, offset) } else { read_u16_be(self.buffer, offset) }
+    }
+
+    /// Read a 32 bits integer
+    /// # Arguments
+    /// * `offset` - The offset of the integer
+    /// # Returns
+    /// * `Option<u32>` - The integer, None if it exceeds the file
+    fn u32(&self, offset: usize) -> Option<u32> {
+        if self.little_endian { read_u32_le(self.buffer, offset) } else { read_u32_be(self.buffer, offset) }
+    }
+}
+
+/// Entry of an IFD describing a tag
+#[derive(Debug, Clone, Copy)]
+struct IfdEntry {
+    tag: u16,
+    field_type: u16,
+    count: usize,
+    // Offset of the values, inside the entry if they fit in 4 bytes
+    values_offset: usize,
+}
+
+/// Give the size of a value of the given field type (TIFF 6.0 section 2, and TIFF
+/// technical note 1 for the IFD type)
+/// # Arguments
+/// * `field_type` - The type of the field
+/// # Returns
+/// * `Option<usize>` - The size in bytes, None if the type is unknown
+fn field_type_size(field_type: u16) -> Option<usize> {
+    match field_type {
+        1 | 2 | 6 | 7 => Some(1),
+        3 | 8 => Some(2),
+        4 | 9 | 11 | 13 => Some(4),
+        5 | 10 | 12 => Some(8),
+        _ => None,
+    }
+}
+
+/// Read the integer values of an entry of type SHORT or LONG
+/// # Arguments
+/// * `reader` - The reader of the file
+/// * `entry` - The entry to read
+/// # Returns
+/// * `Option<Vec<usize>>` - The values, None if the type is not an integer type
+fn read_integers(reader: &TiffReader, entry: &IfdEntry) -> Option<Vec<usize>> {
+    (0..entry.count).map(|index| match entry.field_type {
+        TYPE_SHORT => reader.u16(entry.values_offset + 2 * index).map(usize::from),
+        TYPE_LONG => reader.u32(entry.values_offset + 4 * index).map(|value| value as usize),
+        _ => None,
+    }).collect()
+}
+
+/// Check that the image data located by a pair of offsets and byte counts tags is
+/// inside the file
+/// # Arguments
+/// * `reader` - The reader of the file
+/// * `offsets` - The entry of the offsets tag
+/// * `byte_counts` - The entry of the byte counts tag
+/// * `ifd` - The name of the IFD owning the tags
+/// * `ifd_offset` - The offset of the IFD
+/// # Return
+/// * `()` - If every strip or tile is inside the file
+/// # Errors
+/// * `ValidationError` - If the tags are inconsistent or a strip or tile exceeds the file
+fn check_image_data(reader: &TiffReader, offsets: &IfdEntry, byte_counts: &IfdEntry, ifd: &str,
+                    ifd_offset: usize) -> Result<(), ValidationError> {
+    let error = |tag: u16, defect| InvalidStructure::error(&format!("{} tag {}", ifd, tag), ifd_offset, defect);
+
+    let starts = read_integers(reader, offsets).ok_or_else(|| error(offsets.tag, StructureDefect::InvalidField))?;
+    let lengths = read_integers(reader, byte_counts).ok_or_else(|| error(byte_counts.tag, StructureDefect::InvalidField))?;
+    if starts.len() != lengths.len() {
+        return Err(error(byte_counts.tag, StructureDefect::InvalidField));
+    }
+    for (index, (start, length)) in starts.iter().zip(&lengths).enumerate() {
+        if start.checked_add(*length).is_none_or(|end| end > reader.buffer.len()) {
+            return Err(InvalidStructure::error(&format!("{} tag {} value {}", ifd, offsets.tag, index), *start,
+                                               StructureDefect::Truncated));
+        }
+    }
+    Ok(())
+}
+
+/// Check an IFD and the values of its tags
+/// # Arguments
+/// * `reader` - The reader of the file
+/// * `offset` - The offset of the IFD
+/// * `index` - The position of the IFD in the chain
+/// # Return
+/// * `usize` - The offset of the next IFD, 0 if it is the last one
+/// # Errors
+/// * `ValidationError` - If the IFD or one of its tags is not valid
+fn check_ifd(reader: &TiffReader, offset: usize, index: usize) -> Result<usize, ValidationError> {
+    let ifd = format!("IFD {}", index);
+    let error = |defect| InvalidStructure::error(&ifd, offset, defect);
+
+    let count = reader.u16(offset).ok_or_else(|| error(StructureDefect::Truncated))?;
+    if count == 0 {
+        return Err(error(StructureDefect::InvalidField));
+    }
+    if count > TIFF_MAX_TAGS {
+        return Err(error(StructureDefect::LimitExceeded));
+    }
+    let next_offset_position = offset + 2 + IFD_ENTRY_LENGTH * count as usize;
+    let next = reader.u32(next_offset_position).ok_or_else(|| error(StructureDefect::Truncated))? as usize;
+
+    let mut entries: Vec<IfdEntry> = Vec::with_capacity(count as usize);
+    for position in (offset + 2..next_offset_position).step_by(IFD_ENTRY_LENGTH) {
+        let tag = reader.u16(position).unwrap_or(0);
+        let tag_error = |defect| InvalidStructure::error(&format!("{} tag {}", ifd, tag), position, defect);
+
+        // Tags must be sorted in ascending order
+        if entries.last().is_some_and(|last| last.tag >= tag) {
+            return Err(tag_error(StructureDefect::Misplaced));
+        }
+        let field_type = reader.u16(position + 2).unwrap_or(0);
+        let count = reader.u32(position + 4).unwrap_or(0) as usize;
+        let size = field_type_size(field_type).and_then(|size| size.checked_mul(count))
+            .ok_or_else(|| tag_error(StructureDefect::InvalidField))?;
+        let values_offset = if size <= INLINE_VALUE_LENGTH {
+            position + 8
+        } else {
+            let values_offset = reader.u32(position + 8).unwrap_or(0) as usize;
+            if values_offset.checked_add(size).is_none_or(|end| end > reader.buffer.len()) {
+                return Err(tag_error(StructureDefect::Truncated));
+            }
+            values_offset
+        };
+        entries.push(IfdEntry { tag, field_type, count, values_offset });
+    }
+
+    let find = |tag: u16| entries.iter().find(|entry| entry.tag == tag);
+    for tag in [TAG_IMAGE_WIDTH, TAG_IMAGE_LENGTH] {
+        if find(tag).is_none() {
+            return Err(InvalidStructure::error(&format!("{} tag {}", ifd, tag), offset, StructureDefect::Missing));
+        }
+    }
+    for (offsets_tag, byte_counts_tag) in [(TAG_STRIP_OFFSETS, TAG_STRIP_BYTE_COUNTS), (TAG_TILE_OFFSETS, TAG_TILE_BYTE_COUNTS)] {
+        match (find(offsets_tag), find(byte_counts_tag)) {
+            (Some(offsets), Some(byte_counts)) => check_image_data(reader, offsets, byte_counts, &ifd, offset)?,
+            (None, None) => {},
+            (Some(_), None) => {
+                return Err(InvalidStructure::error(&format!("{} tag {}", ifd, byte_counts_tag), offset, StructureDefect::Missing));
+            },
+            (None, Some(_)) => {
+                return Err(InvalidStructure::error(&format!("{} tag {}", ifd, offsets_tag), offset, StructureDefect::Missing));
+            },
+        }
+    }
+    Ok(next)
+}
+
+/// Validate the structure of a TIFF file, in both byte orders. The chain of IFDs is
+/// walked with loop detection, every value stored at an offset and every strip or
+/// tile must be inside the file, and the number of IFDs and of tags is limited by
+/// `TIFF_MAX_IFDS` and `TIFF_MAX_TAGS`
+/// # Arguments
+/// * `buffer` - The content of the file
+/// # Return
+/// * `()` - If the structure is valid
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the first IFD or tag that is not valid
+pub fn validate_tiff(buffer: &[u8]) -> Result<(), ValidationError> {
+    let little_endian = match buffer.get(..2) {
+        Some(b"II") => true,
+        Some(b"MM") => false,
+        _ => return Err(InvalidStructure::error("header", 0, StructureDefect::InvalidField)),
+    };
+    let reader = TiffReader { buffer, little_endian };
+    if buffer.len() < TIFF_HEADER_LENGTH {
+        return Err(InvalidStructure::error("header", 0, StructureDefect::Truncated));
+    }
+    // BigTIFF files use the magic number 43 and 64 bits offsets
+    if reader.u16(2) != Some(TIFF_MAGIC) {
+        return Err(InvalidStructure::error("header", 0, StructureDefect::Unsupported));
+    }
+
+    let mut offset = reader.u32(4).unwrap_or(0) as usize;
+    if offset < TIFF_HEADER_LENGTH {
+        return Err(InvalidStructure::error("header", 0, StructureDefect::InvalidField));
+    }
+    let mut visited = HashSet::new();
+    while offset != 0 {
+        let index = visited.len();
+        if !visited.insert(offset) {
+            return Err(InvalidStructure::error(&format!("IFD {}", index), offset, StructureDefect::Loop));
+        }
+        if index == TIFF_MAX_IFDS {
+            return Err(InvalidStructure::error(&format!("IFD {}", index), offset, StructureDefect::LimitExceeded));
+        }
+        offset = check_ifd(&reader, offset, index)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_tiff::{validate_tiff, TIFF_MAX_IFDS, TIFF_MAX_TAGS};
+    use crate::validators::file_helper::{read_from_path};
+    use crate::validators::validation_error::{StructureDefect};
+    use crate::validators::test_helper::{invalid, BASE_FILE_PATH};
+
+    /// Build a little-endian IFD entry
+    fn entry(tag: u16, field_type: u16, count: u32, value: u32) -> Vec<u8> {
+        [&tag.to_le_bytes()[..], &field_type.to_le_bytes(), &count.to_le_bytes(), &value.to_le_bytes()].concat()
+    }
+
+    /// Build a little-endian IFD from its entries and the offset of the next IFD
+    fn ifd(entries: &[Vec<u8>], next: u32) -> Vec<u8> {
+        [&(entries.len() as u16).to_le_bytes()[..], &entries.concat(), &next.to_le_bytes()].concat()
+    }
+
+    /// Give the entries of a 1x1 image whose single strip is the first byte following the header
+    fn image_entries() -> Vec<Vec<u8>> {
+        vec![entry(256, 3, 1, 1), entry(257, 3, 1, 1), entry(273, 4, 1, 8), entry(279, 4, 1, 1)]
+    }
+
+    /// Build a little-endian TIFF file with the IFDs chained after a strip of one byte
+    fn tiff(ifds: &[Vec<u8>]) -> Vec<u8> {
+        let mut file = b"II\x2a\x00\x0a\x00\x00\x00\x00\x00".to_vec();
+        ifds.iter().for_each(|ifd| file.extend_from_slice(ifd));
+        file
+    }
+
+    #[test]
+    fn validate_tiff_classical() {
+        let file = read_from_path(&format!("{}images/file_example_tiff.tif", BASE_FILE_PATH)).unwrap();
+
+        // Pass
+        assert_eq!(validate_tiff(&file), Ok(()));
+        assert_eq!(validate_tiff(&tiff(&[ifd(&image_entries(), 0)])), Ok(()));
+        // Big-endian with two IFDs
+        let mut big_endian = b"MM\x00\x2a\x00\x00\x00\x08".to_vec();
+        for next in [38u32, 0] {
+            big_endian.extend_from_slice(&2u16.to_be_bytes());
+            for tag in [256u16, 257] {
+                big_endian.extend_from_slice(&[&tag.to_be_bytes()[..], &3u16.to_be_bytes(), &1u32.to_be_bytes(), &[0, 1, 0, 0]].concat());
+            }
+            big_endian.extend_from_slice(&next.to_be_bytes());
+        }
+        assert_eq!(validate_tiff(&big_endian), Ok(()));
+
+        // Fail
+        // Truncated before the IFD
+        assert_eq!(validate_tiff(&file[..1_000_000]), invalid("IFD 0", 1128408, StructureDefect::Truncated));
+
+        // Corner cases
+        assert_eq!(validate_tiff(b""), invalid("header", 0, StructureDefect::InvalidField));
+        assert_eq!(validate_tiff(b"II\x2a\x00"), invalid("header", 0, StructureDefect::Truncated));
+        assert_eq!(validate_tiff(b"II\x2b\x00\x08\x00\x00\x00"), invalid("header", 0, StructureDefect::Unsupported));
+        assert_eq!(validate_tiff(b"II\x2a\x00\x04\x00\x00\x00"), invalid("header", 0, StructureDefect::InvalidField));
+    }
+
+    #[test]
+    fn validate_tiff_ifd_chain() {
+        let first = ifd(&image_entries(), 0);
+        let second_offset = 10 + first.len() as u32;
+
+        // Fail
+        // Loop to itself and to the first IFD
+        assert_eq!(validate_tiff(&tiff(&[ifd(&image_entries(), 10)])), invalid("IFD 1", 10, StructureDefect::Loop));
+        let file = tiff(&[ifd(&image_entries(), second_offset), ifd(&image_entries(), 10)]);
+        assert_eq!(validate_tiff(&file), invalid("IFD 2", 10, StructureDefect::Loop));
+        // Next IFD outside of the file
+        let file = tiff(&[ifd(&image_entries(), 0xffff)]);
+        assert_eq!(validate_tiff(&file), invalid("IFD 1", 0xffff, StructureDefect::Truncated));
+
+        // Corner cases
+        let ifd_length = first.len() as u32;
+        let ifds: Vec<Vec<u8>> = (1..=TIFF_MAX_IFDS as u32 + 1).map(|index| ifd(&image_entries(), 10 + index * ifd_length)).collect();
+        let file = tiff(&ifds);
+        assert_eq!(validate_tiff(&file), invalid(&format!("IFD {}", TIFF_MAX_IFDS), 10 + TIFF_MAX_IFDS * ifd_length as usize,
+                                                 StructureDefect::LimitExceeded));
+        assert_eq!(validate_tiff(&tiff(&[ifd(&[], 0)])), invalid("IFD 0", 10, StructureDefect::InvalidField));
+        let mut many_tags = tiff(&[]);
+        many_tags.extend_from_slice(&(TIFF_MAX_TAGS + 1).to_le_bytes());
+        assert_eq!(validate_tiff(&many_tags), invalid("IFD 0", 10, StructureDefect::LimitExceeded));
+    }
+
+    #[test]
+    fn validate_tiff_tags() {
+        let entry_offset = |index: usize| 12 + 12 * index;
+
+        // Fail
+        // Values stored outside of the file
+        let mut entries = image_entries();
+        entries.insert(2, entry(270, 2, 20, 0x1000));
+        assert_eq!(validate_tiff(&tiff(&[ifd(&entries, 0)])), invalid("IFD 0 tag 270", entry_offset(2), StructureDefect::Truncated));
+        // Count of values exceeding the file
+        let mut entries = image_entries();
+        entries.push(entry(280, 4, u32::MAX, 0));
+        assert_eq!(validate_tiff(&tiff(&[ifd(&entries, 0)])), invalid("IFD 0 tag 280", entry_offset(4), StructureDefect::Truncated));
+        // Unknown type and unsorted tags
+        let mut entries = image_entries();
+        entries[1] = entry(257, 99, 1, 1);
+        assert_eq!(validate_tiff(&tiff(&[ifd(&entries, 0)])), invalid("IFD 0 tag 257", entry_offset(1), StructureDefect::InvalidField));
+        let mut entries = image_entries();
+        entries.swap(0, 1);
+        assert_eq!(validate_tiff(&tiff(&[ifd(&entries, 0)])), invalid("IFD 0 tag 256", entry_offset(1), StructureDefect::Misplaced));
+        // Strip outside of the file
+        let mut entries = image_entries();
+        entries[3] = entry(279, 4, 1, 0x1000);
+        assert_eq!(validate_tiff(&tiff(&[ifd(&entries, 0)])), invalid("IFD 0 tag 273 value 0", 8, StructureDefect::Truncated));
+
+        // Corner cases
+        let entries = image_entries();
+        assert_eq!(validate_tiff(&tiff(&[ifd(&entries[1..], 0)])), invalid("IFD 0 tag 256", 10, StructureDefect::Missing));
+        assert_eq!(validate_tiff(&tiff(&[ifd(&entries[..3], 0)])), invalid("IFD 0 tag 279", 10, StructureDefect::Missing));
+        let mut entries = image_entries();
+        entries[3] = entry(279, 4, 2, 0);
+        assert_eq!(validate_tiff(&tiff(&[ifd(&entries, 0)])), invalid("IFD 0 tag 279", 10, StructureDefect::InvalidField));
+    }
+}
@@ -2,8 +2,8 @@ use uuid::Uuid;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::validators::error_messages::{INVALID_UUID};
 use crate::validators::file_helper::{read_from_path};
+use crate::validators::validation_error::{ValidationError};
 
 // https://fr.wikipedia.org/wiki/Universally_unique_identifier
 static REGEX_UUID: &str = r"[[:xdigit:]]{8}\-([[:xdigit:]]{4}\-){3}[[:xdigit:]]{12}";
@@ -25,12 +25,12 @@ pub fn validate_uuid(uuid: &str) -> bool {
 /// * `file_path` - path to file
 /// * `provided_uuid` - uuid in hyphenated format that the file must contain
 /// # Returns
-/// * `Result<bool, &str>` - True if the uuid is valid, false otherwise
+/// * `bool` - True if the uuid is valid, false otherwise
 /// # Errors
-/// * `&str` - An error message if the uuid is invalid
-pub fn validate_file_uuid<'a>(file_path: &'a str, provided_uuid: &'a str) -> Result<bool, &'a str> {
+/// * `ValidationError` - If the uuid is invalid or the file can't be read
+pub fn validate_file_uuid(file_path: &str, provided_uuid: &str) -> Result<bool, ValidationError> {
     if !validate_uuid(provided_uuid) {
-        return Err(INVALID_UUID);
+        return Err(ValidationError::InvalidUuid);
     }
 
     let file_buffer = read_from_path(file_path)?;
@@ -44,14 +44,14 @@ mod tests {
     use uuid::Uuid;
 
     use crate::validators::validate_uuid::{validate_uuid, validate_file_uuid};
-    use crate::validators::test_helper::{result_helper};
+    use crate::validators::test_helper::{result_helper, BASE_FILE_PATH};
     use crate::validators::file_helper::{read_from_path};
-    use crate::validators::error_messages::{INVALID_UUID, ERROR_READING_FILE};
+    use crate::validators::validation_error::{ValidationError};
+    use std::io::ErrorKind;
 
     // Tests has been written with file example found here:
     // https://file-examples.com/
     // Files are on my github but not delivered for the scholar rendering
-    static BASE_FILE_PATH : &str = "A:/HEIG/Semestre 6/SEC/Labos/SEC_Labo1/files/";
     static IMAGES_FOLDER : &str = "images/";
     static NAMING_CONVENTION : &str = "file_example_";
 
@@ -161,7 +161,7 @@ mod tests {
         result_helper(validate_file_uuid(
             &format!("{}{}{}{}", BASE_FILE_PATH, IMAGES_FOLDER, NAMING_CONVENTION, "png.png"),
             "00000008_0004-0004-0004-000000000012"),
-                      false, Some(INVALID_UUID));
+                      false, Some(ValidationError::InvalidUuid));
     }
 
     #[test]
@@ -170,6 +170,6 @@ mod tests {
         result_helper(validate_file_uuid(
             &format!("{}{}{}{}", BASE_FILE_PATH, IMAGES_FOLDER, NAMING_CONVENTION, "test.test"),
             "00000008-0004-0004-0004-000000000012"),
-                      false, Some(ERROR_READING_FILE));
+                      false, Some(ValidationError::ReadingFile(ErrorKind::NotFound)));
     }
 }
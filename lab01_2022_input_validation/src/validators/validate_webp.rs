@@ -0,0 +1,263 @@
+use crate::validators::riff_helper::{check_riff_header, read_riff_chunks, RiffChunk};
+use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+
+// Start code of a VP8 key frame, following the 3 bytes of the frame tag
+static VP8_START_CODE: [u8; 3] = [0x9d, 0x01, 0x2a];
+const VP8_HEADER_LENGTH: usize = 10;
+const VP8L_SIGNATURE: u8 = 0x2f;
+const VP8L_HEADER_LENGTH: usize = 5;
+const VP8X_LENGTH: usize = 10;
+// Flags of the VP8X chunk announcing an ICC profile and an animation
+const VP8X_ICC_FLAG: u8 = 0x20;
+const VP8X_ANIMATION_FLAG: u8 = 0x02;
+// The product of the canvas dimensions must fit in 32 bits
+const VP8X_MAX_AREA: u64 = u32::MAX as u64;
+
+/// Check the header of a VP8 key frame
+/// # Arguments
+/// * `chunk` - The "VP8 " chunk
+/// # Return
+/// * `()` - If the frame header is valid
+/// # Errors
+/// * `ValidationError` - If the frame is not a key frame or its dimensions are invalid
+fn check_vp8(chunk: &RiffChunk) -> Result<(), ValidationError> {
+    let data = chunk.data;
+    if data.len() < VP8_HEADER_LENGTH {
+        return Err(chunk.error(StructureDefect::Truncated));
+    }
+    let width = u16::from_le_bytes([data[6], data[7]]) & 0x3fff;
+    let height = u16::from_le_bytes([data[8], data[9]]) & 0x3fff;
+    // The first bit of the frame tag is 0 for a key frame
+    if data[0] & 0x01 != 0 || data[3..6] != VP8_START_CODE || width == 0 || height == 0 {
+        return Err(chunk.error(StructureDefect::InvalidField));
+    }
+    Ok(())
+}
+
+/// Check the header of a VP8L lossless bitstream
+/// # Arguments
+/// * `chunk` - The "VP8L" chunk
+/// # Return
+/// * `()` - If the header is valid
+/// # Errors
+/// * `ValidationError` - If the signature or the version is invalid
+fn check_vp8l(chunk: &RiffChunk) -> Result<(), ValidationError> {
+    let data = chunk.data;
+    if data.len() < VP8L_HEADER_LENGTH {
+        return Err(chunk.error(StructureDefect::Truncated));
+    }
+    // The three last bits of the header are the version, which must be 0
+    if data[0] != VP8L_SIGNATURE || data[4] >> 5 != 0 {
+        return Err(chunk.error(StructureDefect::InvalidField));
+    }
+    Ok(())
+}
+
+/// Check the image chunk of a still image, lossy or lossless
+/// # Arguments
+/// * `chunk` - The chunk to check
+/// # Return
+/// * `bool` - True if the chunk is an image chunk, false otherwise
+/// # Errors
+/// * `ValidationError` - If the image chunk is not valid
+fn check_image(chunk: &RiffChunk) -> Result<bool, ValidationError> {
+    match &chunk.id {
+        b"VP8 " => check_vp8(chunk).map(|_| true),
+        b"VP8L" => check_vp8l(chunk).map(|_| true),
+        _ => Ok(false),
+    }
+}
+
+/// Check the chunks of the extended format following the VP8X chunk
+/// # Arguments
+/// * `header` - The VP8X chunk
+/// * `chunks` - The chunks following the VP8X chunk
+/// # Return
+/// * `()` - If the layout is valid
+/// # Errors
+/// * `ValidationError` - If the VP8X chunk is invalid or a chunk is misplaced or missing
+fn check_extended(header: &RiffChunk, chunks: &[RiffChunk]) -> Result<(), ValidationError> {
+    let data = header.data;
+    if data.len() != VP8X_LENGTH {
+        return Err(header.error(StructureDefect::InvalidField));
+    }
+    let width = u64::from(u32::from_le_bytes([data[4], data[5], data[6], 0])) + 1;
+    let height = u64::from(u32::from_le_bytes([data[7], data[8], data[9], 0])) + 1;
+    if width * height > VP8X_MAX_AREA {
+        return Err(header.error(StructureDefect::InvalidField));
+    }
+    let animated = data[0] & VP8X_ANIMATION_FLAG != 0;
+
+    let mut has_image = false;
+    let mut has_animation = false;
+    let mut frames = 0;
+    for (index, chunk) in chunks.iter().enumerate() {
+        let misplaced = match &chunk.id {
+            b"VP8X" => true,
+            // The ICC profile must precede the image data
+            b"ICCP" => data[0] & VP8X_ICC_FLAG == 0 || index != 0,
+            b"ANIM" => !animated || has_animation,
+            b"ANMF" => !has_animation,
+            // The alpha channel must immediately precede a lossy image
+            b"ALPH" => animated || chunks.get(index + 1).is_none_or(|next| &next.id != b"VP8 "),
+            b"VP8 " | b"VP8L" => animated || has_image,
+            _ => false,
+        };
+        if misplaced {
+            return Err(chunk.error(StructureDefect::Misplaced));
+        }
+        has_image |= check_image(chunk)?;
+        has_animation |= &chunk.id == b"ANIM";
+        frames += usize::from(&chunk.id == b"ANMF");
+    }
+
+    let missing = if animated && !has_animation {
+        Some("ANIM")
+    } else if animated && frames == 0 {
+        Some("ANMF")
+    } else if !animated && !has_image {
+        Some("VP8 ")
+    } else {
+        None
+    };
+    match missing {
+        Some(part) => Err(InvalidStructure::error(part, header.offset, StructureDefect::Missing)),
+        None => Ok(()),
+    }
+}
+
+/// Validate the structure of a WebP file. The size of the RIFF header must match the
+/// length of the file, a simple file must contain a single VP8 or VP8L image chunk and
+/// an extended file must start with a VP8X chunk followed by its image or animation
+/// # Arguments
+/// * `buffer` - The content of the file
+/// # Return
+/// * `()` - If the structure is valid
+/// # Errors
+/// * `ValidationError` - `InvalidFileStructure` with the first chunk that is not valid
+pub fn validate_webp(buffer: &[u8]) -> Result<(), ValidationError> {
+    let start = check_riff_header(buffer, b"WEBP")?;
+    let chunks = read_riff_chunks(buffer, start, buffer.len())?;
+
+    let Some((first, following)) = chunks.split_first() else {
+        return Err(InvalidStructure::error("VP8 ", start, StructureDefect::Missing));
+    };
+    if &first.id == b"VP8X" {
+        return check_extended(first, following);
+    }
+    if !check_image(first)? {
+        return Err(first.error(StructureDefect::Unsupported));
+    }
+    match following.first() {
+        Some(chunk) => Err(chunk.error(StructureDefect::Misplaced)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validators::validate_webp::{validate_webp};
+    use crate::validators::file_helper::{read_from_path};
+    use crate::validators::riff_helper::{riff_chunk};
+    use crate::validators::validation_error::{StructureDefect};
+    use crate::validators::test_helper::{invalid, BASE_FILE_PATH};
+
+    // Key frame header of 1x1 pixel
+    static VP8: [u8; 10] = [0x10, 0x00, 0x00, 0x9d, 0x01, 0x2a, 0x01, 0x00, 0x01, 0x00];
+    static VP8L: [u8; 5] = [0x2f, 0x00, 0x00, 0x00, 0x00];
+    // Canvas of 1x1 pixel with the given flags
+    static VP8X: [u8; 10] = [0x00; 10];
+
+    /// Build a WebP file from the given chunks
+    fn webp(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let body = chunks.concat();
+        let mut file = b"RIFF".to_vec();
+        file.extend_from_slice(&(body.len() as u32 + 4).to_le_bytes());
+        file.extend_from_slice(b"WEBP");
+        file.extend_from_slice(&body);
+        file
+    }
+
+    /// Give the VP8X chunk with the given flags
+    fn vp8x(flags: u8) -> Vec<u8> {
+        let mut data = VP8X;
+        data[0] = flags;
+        riff_chunk(b"VP8X", &data)
+    }
+
+    #[test]
+    fn validate_webp_classical() {
+        let file = read_from_path(&format!("{}images/file_example_webp.webp", BASE_FILE_PATH)).unwrap();
+
+        // Pass
+        assert_eq!(validate_webp(&file), Ok(()));
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8 ", &VP8)])), Ok(()));
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8L", &VP8L)])), Ok(()));
+
+        // Fail
+        let mut junk = file.clone();
+        junk.extend_from_slice(b"<?php echo 'test'; ?>");
+        assert_eq!(validate_webp(&junk), invalid("RIFF", file.len(), StructureDefect::TrailingData));
+        assert_eq!(validate_webp(&file[..file.len() - 1]), invalid("RIFF", 0, StructureDefect::Truncated));
+        // RIFF size consistent with a truncated chunk
+        let mut truncated = file[..100].to_vec();
+        truncated[4..8].copy_from_slice(&92u32.to_le_bytes());
+        assert_eq!(validate_webp(&truncated), invalid("VP8 ", 12, StructureDefect::Truncated));
+
+        // Corner cases
+        assert_eq!(validate_webp(&webp(&[])), invalid("VP8 ", 12, StructureDefect::Missing));
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"EXIF", &[0])])), invalid("EXIF", 12, StructureDefect::Unsupported));
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8 ", &VP8), riff_chunk(b"EXIF", &[0])])),
+                   invalid("EXIF", 30, StructureDefect::Misplaced));
+    }
+
+    #[test]
+    fn validate_webp_image_chunks() {
+        // Fail
+        let mut inter_frame = VP8;
+        inter_frame[0] = 0x11;
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8 ", &inter_frame)])), invalid("VP8 ", 12, StructureDefect::InvalidField));
+        let mut start_code = VP8;
+        start_code[4] = 0x02;
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8 ", &start_code)])), invalid("VP8 ", 12, StructureDefect::InvalidField));
+        let mut signature = VP8L;
+        signature[0] = 0x2e;
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8L", &signature)])), invalid("VP8L", 12, StructureDefect::InvalidField));
+
+        // Corner cases
+        let mut no_width = VP8;
+        no_width[6] = 0;
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8 ", &no_width)])), invalid("VP8 ", 12, StructureDefect::InvalidField));
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8 ", &VP8[..9])])), invalid("VP8 ", 12, StructureDefect::Truncated));
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8L", &VP8L[..4])])), invalid("VP8L", 12, StructureDefect::Truncated));
+    }
+
+    #[test]
+    fn validate_webp_extended() {
+        let alpha = riff_chunk(b"ALPH", &[0]);
+        let image = riff_chunk(b"VP8 ", &VP8);
+        let animation = riff_chunk(b"ANIM", &[0; 6]);
+        let frame = riff_chunk(b"ANMF", &[0; 16]);
+
+        // Pass
+        assert_eq!(validate_webp(&webp(&[vp8x(0x10), alpha.clone(), image.clone(), riff_chunk(b"EXIF", &[0])])), Ok(()));
+        assert_eq!(validate_webp(&webp(&[vp8x(0x20), riff_chunk(b"ICCP", &[0]), riff_chunk(b"VP8L", &VP8L)])), Ok(()));
+        assert_eq!(validate_webp(&webp(&[vp8x(0x02), animation.clone(), frame.clone(), frame.clone()])), Ok(()));
+
+        // Fail
+        assert_eq!(validate_webp(&webp(&[vp8x(0x00)])), invalid("VP8 ", 12, StructureDefect::Missing));
+        assert_eq!(validate_webp(&webp(&[vp8x(0x02), animation.clone()])), invalid("ANMF", 12, StructureDefect::Missing));
+        assert_eq!(validate_webp(&webp(&[vp8x(0x02), frame.clone()])), invalid("ANMF", 30, StructureDefect::Misplaced));
+        assert_eq!(validate_webp(&webp(&[vp8x(0x00), animation])), invalid("ANIM", 30, StructureDefect::Misplaced));
+        assert_eq!(validate_webp(&webp(&[vp8x(0x00), image.clone(), alpha.clone()])), invalid("ALPH", 48, StructureDefect::Misplaced));
+        assert_eq!(validate_webp(&webp(&[vp8x(0x00), riff_chunk(b"ICCP", &[0]), image.clone()])),
+                   invalid("ICCP", 30, StructureDefect::Misplaced));
+        assert_eq!(validate_webp(&webp(&[vp8x(0x00), image.clone(), image.clone()])), invalid("VP8 ", 48, StructureDefect::Misplaced));
+
+        // Corner cases
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8X", &[0; 9])])), invalid("VP8X", 12, StructureDefect::InvalidField));
+        let mut huge = VP8X;
+        huge[4..10].copy_from_slice(&[0xff; 6]);
+        assert_eq!(validate_webp(&webp(&[riff_chunk(b"VP8X", &huge), image])), invalid("VP8X", 12, StructureDefect::InvalidField));
+    }
+}
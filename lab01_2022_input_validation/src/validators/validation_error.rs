@@ -0,0 +1,214 @@
+use std::error::Error;
+use std::fmt;
+use std::io::ErrorKind;
+
+use crate::validators::error_messages::{INVALID_URL, INVALID_WHITELIST_TOP_LEVEL_DOMAIN, INVALID_WHITELIST_SCHEME,
+                                        INVALID_PUBLIC_SUFFIX_LIST, INVALID_HOST_LIST, INVALID_EMAIL, INVALID_FILE_PATH,
+                                        INVALID_FILE_GROUP, INVALID_FILE_TYPE, INVALID_FILE_STRUCTURE,
+                                        ERROR_READING_FILE, INVALID_UUID};
+
+/// Component of an url that has been rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlComponent {
+    Scheme,
+    Userinfo,
+    InternationalizedDomain,
+    Hostname,
+    SubDomain,
+    TopLevelDomain,
+    RegistrableDomain,
+    IpAddress,
+    InternalHost,
+    HostList,
+    Port,
+    Query,
+    Following,
+}
+
+impl fmt::Display for UrlComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            UrlComponent::Scheme => "scheme",
+            UrlComponent::Userinfo => "userinfo",
+            UrlComponent::InternationalizedDomain => "internationalized domain name",
+            UrlComponent::Hostname => "hostname",
+            UrlComponent::SubDomain => "sub domain",
+            UrlComponent::TopLevelDomain => "top level domain",
+            UrlComponent::RegistrableDomain => "registrable domain",
+            UrlComponent::IpAddress => "ip address",
+            UrlComponent::InternalHost => "internal host",
+            UrlComponent::HostList => "host not allowed",
+            UrlComponent::Port => "port",
+            UrlComponent::Query => "query",
+            UrlComponent::Following => "path or fragment",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Component of an email address that has been rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailComponent {
+    LocalPart,
+    Domain,
+    Length,
+}
+
+impl fmt::Display for EmailComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EmailComponent::LocalPart => "local part",
+            EmailComponent::Domain => "domain",
+            EmailComponent::Length => "length",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Rule broken by a part of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureDefect {
+    /// The part ends after the end of the file
+    Truncated,
+    /// The part is not at its expected position
+    Misplaced,
+    /// A mandatory part is missing
+    Missing,
+    /// A field of the part has an invalid value
+    InvalidField,
+    /// The checksum of the part does not match its content
+    InvalidChecksum,
+    /// The part is a critical part unknown to the validator
+    Unsupported,
+    /// Data follows the end of the file format
+    TrailingData,
+    /// The part refers to a part already visited, such as a cycle of offsets
+    Loop,
+    /// The file contains more parts than the validator accepts
+    LimitExceeded,
+}
+
+impl fmt::Display for StructureDefect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StructureDefect::Truncated => "truncated",
+            StructureDefect::Misplaced => "misplaced",
+            StructureDefect::Missing => "missing",
+            StructureDefect::InvalidField => "invalid field",
+            StructureDefect::InvalidChecksum => "invalid checksum",
+            StructureDefect::Unsupported => "unsupported",
+            StructureDefect::TrailingData => "trailing data",
+            StructureDefect::Loop => "loop",
+            StructureDefect::LimitExceeded => "limit exceeded",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Part of a file whose structure is not valid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidStructure {
+    /// The name of the rejected part, such as the type of a PNG chunk
+    pub part: String,
+    /// The offset of the part from the start of the file, or the length of
+    /// the file if the part is missing
+    pub offset: usize,
+    /// The rule broken by the part
+    pub defect: StructureDefect,
+}
+
+impl InvalidStructure {
+    /// Create the error of a rejected part
+    /// # Arguments
+    /// * `part` - The name of the part
+    /// * `offset` - The offset of the part from the start of the file
+    /// * `defect` - The rule broken by the part
+    /// # Returns
+    /// * `ValidationError` - The `InvalidFileStructure` error
+    pub fn error(part: &str, offset: usize, defect: StructureDefect) -> ValidationError {
+        ValidationError::InvalidFileStructure(InvalidStructure { part: part.to_string(), offset, defect })
+    }
+}
+
+impl fmt::Display for InvalidStructure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}: {}", self.part, self.offset, self.defect)
+    }
+}
+
+/// Error returned by the validators when an input cannot be validated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The url is not valid, contains the component that has been rejected
+    InvalidUrl(UrlComponent),
+    /// A top level domain given in the whitelist is not valid,
+    /// contains the index of the entry in the whitelist
+    InvalidWhitelistTopLevelDomain(usize),
+    /// A scheme given in the whitelist is not valid,
+    /// contains the index of the entry in the whitelist
+    InvalidWhitelistScheme(usize),
+    /// The public suffix list file is not valid utf-8 or contains no rule
+    InvalidPublicSuffixList,
+    /// An entry of the host list is not valid, contains the line number of the entry
+    InvalidHostList(usize),
+    /// The email address is not valid, contains the component that has been rejected
+    InvalidEmail(EmailComponent),
+    /// The file path contains forbidden characters
+    InvalidFilePath,
+    /// The file could not be read, contains the kind of the I/O error
+    ReadingFile(ErrorKind),
+    /// The file is neither an image nor a video
+    InvalidFileGroup,
+    /// The type of the file could not be determined
+    InvalidFileType,
+    /// The content of the file does not follow its format, contains the rejected part
+    InvalidFileStructure(InvalidStructure),
+    /// The UUID is not in hyphenated format
+    InvalidUuid,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::InvalidUrl(component) => write!(f, "{} ({})", INVALID_URL, component),
+            ValidationError::InvalidWhitelistTopLevelDomain(index) =>
+                write!(f, "{} (index {})", INVALID_WHITELIST_TOP_LEVEL_DOMAIN, index),
+            ValidationError::InvalidWhitelistScheme(index) =>
+                write!(f, "{} (index {})", INVALID_WHITELIST_SCHEME, index),
+            ValidationError::InvalidPublicSuffixList => write!(f, "{}", INVALID_PUBLIC_SUFFIX_LIST),
+            ValidationError::InvalidHostList(line) => write!(f, "{} (line {})", INVALID_HOST_LIST, line),
+            ValidationError::InvalidEmail(component) => write!(f, "{} ({})", INVALID_EMAIL, component),
+            ValidationError::InvalidFilePath => write!(f, "{}", INVALID_FILE_PATH),
+            ValidationError::ReadingFile(kind) => write!(f, "{} ({})", ERROR_READING_FILE, kind),
+            ValidationError::InvalidFileGroup => write!(f, "{}", INVALID_FILE_GROUP),
+            ValidationError::InvalidFileType => write!(f, "{}", INVALID_FILE_TYPE),
+            ValidationError::InvalidFileStructure(structure) => write!(f, "{} ({})", INVALID_FILE_STRUCTURE, structure),
+            ValidationError::InvalidUuid => write!(f, "{}", INVALID_UUID),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use crate::validators::validation_error::{ValidationError, InvalidStructure, StructureDefect};
+    use crate::validators::error_messages::{INVALID_FILE_PATH, INVALID_UUID};
+
+    #[test]
+    fn validation_error_display() {
+        // Pass
+        assert_eq!(ValidationError::InvalidFilePath.to_string(), INVALID_FILE_PATH);
+        assert_eq!(ValidationError::InvalidUuid.to_string(), INVALID_UUID);
+
+        // Corner cases
+        // Variants carrying data must show it in the message
+        assert!(ValidationError::InvalidWhitelistTopLevelDomain(2).to_string().contains("index 2"));
+        assert!(ValidationError::ReadingFile(ErrorKind::NotFound).to_string()
+            .contains(&ErrorKind::NotFound.to_string()));
+        assert!(InvalidStructure::error("IEND", 42, StructureDefect::TrailingData).to_string()
+            .contains("IEND at offset 42: trailing data"));
+    }
+}